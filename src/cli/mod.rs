@@ -1,4 +1,7 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+
+use crate::config::parse_variable;
 
 #[derive(Parser)]
 #[command(name = "devkit")]
@@ -6,27 +9,126 @@ use clap::{Parser, Subcommand};
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+
+    /// Read templates from this directory before the embedded copies
+    /// (default = $DEVKIT_TEMPLATES)
+    #[arg(long, global = true, value_name = "DIR")]
+    pub templates: Option<PathBuf>,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     /// Launch the interactive interface to configure the project
     Init {
-        #[arg(short, long, default_value = ".")]
-        path: String,
+        /// Target path (prompted in the interface when omitted)
+        #[arg(short, long)]
+        path: Option<String>,
     },
 
     /// Directly configure a specific stack
     Config {
-        /// One or more stacks to apply (repeatable: --stack rls --stack wasm)
+        /// One or more stacks as stack[:variant][@dir] (e.g. vue@frontend fastapi@api)
         #[arg(required = true)]
         stacks: Vec<String>,
 
         /// Optional target path for generated files (default = current dir)
         #[arg(short, long)]
         path: Option<String>,
+
+        /// Scaffold a minimal starter app when the target is empty or missing
+        #[arg(long)]
+        scaffold: bool,
+
+        /// Render Dockerfiles with a non-root user, `--chown` copies and an
+        /// optional runtime digest (--var runtime_digest=@sha256:...)
+        #[arg(long)]
+        hardened: bool,
+
+        /// Write Kubernetes manifests to k8s/, running the local compose images
+        /// unless a registry is set
+        /// (--var image_registry=ghcr.io/acme --var image_tag=1.0 --var ingress_host=app.example.com)
+        #[arg(long)]
+        k8s: bool,
+
+        /// Entrypoint routing / and /api in compose.yaml when a frontend
+        /// and a backend are selected
+        #[arg(long, value_enum, default_value_t = ProxyServer::Caddy)]
+        proxy: ProxyServer,
+
+        /// Add-on component layered on the stacks (repeatable: --with husky --with gitignore)
+        #[arg(long = "with", value_name = "COMPONENT")]
+        components: Vec<String>,
+
+        /// Template variable override (repeatable: --var wasm_tool=wasm-pack), or
+        /// for a single stack with a stack prefix (--var express.app_port=4000)
+        #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_variable)]
+        vars: Vec<(String, String)>,
+
+        /// Progress output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+
+    /// Refresh the .mk files and Dockerfiles from the templates embedded in this binary
+    SyncMk {
+        /// Project configured by devkit (default = current dir)
+        #[arg(short, long)]
+        path: Option<String>,
+
+        /// Template variable override (repeatable: --var wasm_tool=wasm-pack), or
+        /// for a single stack with a stack prefix (--var express.app_port=4000)
+        #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_variable)]
+        vars: Vec<(String, String)>,
+
+        /// Progress output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+
+    /// Check that local toolchain pins agree with the Dockerfiles
+    Status {
+        /// Project configured by devkit (default = current dir)
+        #[arg(short, long)]
+        path: Option<String>,
     },
 
-    /// List all available stacks
+    /// List all available stacks and components
     List,
+
+    /// Manage the templates embedded in the binary
+    Templates {
+        #[command(subcommand)]
+        command: TemplatesCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TemplatesCommand {
+    /// Write every embedded template to a directory
+    Export {
+        /// Destination directory, usable afterwards with --templates
+        dir: String,
+
+        /// Progress output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum ProxyServer {
+    /// Caddyfile with reverse_proxy routes
+    Caddy,
+    /// Traefik file-provider routes
+    Traefik,
+    /// No entrypoint service
+    None,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable terminal output
+    Text,
+    /// One JSON event per line
+    Json,
 }
@@ -0,0 +1,220 @@
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::Path;
+
+use super::{
+    load_stack, registry, template, MakeFragment, StackConfig, StackSpec, Variables,
+    AVAILABLE_STACKS,
+};
+
+/// Stack placed in a directory of the target, selected or already configured
+struct Placement {
+    label: String,
+    dir: String,
+    selected: bool,
+    config: StackConfig,
+    lineage: Vec<String>,
+}
+
+/// Check the selected stacks against each other and against the stacks already
+/// configured in the target, before anything is written. Every problem is
+/// reported in a single error; harmless overlaps are returned as warnings.
+pub fn check_stacks(specs: &[String], target_path: &str) -> Result<Vec<String>> {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    let mut placements: Vec<Placement> = Vec::new();
+
+    for spec in specs {
+        let parsed = StackSpec::parse(spec);
+        if placements.iter().any(|p| p.config.id == parsed.stack) {
+            errors.push(format!("{} is selected more than once", parsed.stack));
+            continue;
+        }
+        placements.push(Placement {
+            label: parsed.stack.to_string(),
+            dir: parsed.dir().to_string(),
+            selected: true,
+            config: load_stack(spec)?,
+            lineage: registry::lineage(parsed.stack)?,
+        });
+    }
+
+    for (id, dir) in installed_stacks(Path::new(target_path)) {
+        if placements.iter().any(|p| p.config.id == id) {
+            continue;
+        }
+        warnings.push(format!(
+            "{}.mk is already in {} but {} is not selected; the new Makefile will not include it",
+            id, target_path, id
+        ));
+        placements.push(Placement {
+            label: format!("{} (already configured)", id),
+            dir,
+            selected: false,
+            config: load_stack(&id)?,
+            lineage: registry::lineage(&id)?,
+        });
+    }
+
+    for placement in placements.iter().filter(|p| p.selected) {
+        for required in &placement.config.requires {
+            if !placements.iter().any(|p| p.lineage.contains(required)) {
+                errors.push(format!("{} requires {}", placement.label, required));
+            }
+        }
+    }
+
+    for (i, a) in placements.iter().enumerate() {
+        for b in placements.iter().skip(i + 1).filter(|b| b.dir == a.dir) {
+            let declared = a
+                .config
+                .conflicts
+                .iter()
+                .find(|c| b.lineage.contains(c))
+                .or_else(|| b.config.conflicts.iter().find(|c| a.lineage.contains(c)));
+            if let Some(conflict) = declared {
+                errors.push(format!(
+                    "{} and {} cannot share '{}' (one {} stack per directory); \
+                     place them in separate directories with stack@dir",
+                    a.label, b.label, a.dir, conflict
+                ));
+                continue;
+            }
+
+            for file in &a.config.files {
+                let Some(other) = b.config.files.iter().find(|f| f.path == file.path) else {
+                    continue;
+                };
+                if rendered(&a.config, &file.content) == rendered(&b.config, &other.content) {
+                    warnings.push(format!(
+                        "{} is written identically by {} and {}",
+                        file.path, a.label, b.label
+                    ));
+                } else {
+                    errors.push(format!(
+                        "{} in '{}' would be written by both {} and {}",
+                        file.path, a.dir, a.label, b.label
+                    ));
+                }
+            }
+        }
+    }
+
+    let mut recipes: Vec<(String, &str)> = Vec::new();
+    for placement in &placements {
+        for target in make_recipes(&placement.config) {
+            match recipes.iter().find(|(name, _)| *name == target) {
+                Some((_, owner)) => errors.push(format!(
+                    "make target {} is defined by both {} and {}",
+                    target, owner, placement.label
+                )),
+                None => recipes.push((target, &placement.label)),
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(warnings)
+    } else {
+        Err(anyhow!(
+            "Incompatible stack selection:\n  - {}",
+            errors.join("\n  - ")
+        ))
+    }
+}
+
+/// Template content with the stack defaults filled in
+fn rendered(config: &StackConfig, content: &str) -> String {
+    let mut vars: Variables = config
+        .variables
+        .iter()
+        .map(|v| (v.name.clone(), v.default.clone()))
+        .collect();
+    vars.insert("stack".to_string(), config.id.clone());
+    template::render(content, &vars)
+}
+
+/// Targets with a recipe in the stack `.mk`; prerequisite-only aliases may repeat
+pub(super) fn make_recipes(config: &StackConfig) -> Vec<String> {
+    let Some(file) = config
+        .files
+        .iter()
+        .find(|f| f.path == config.makefile_path())
+    else {
+        return Vec::new();
+    };
+    MakeFragment::parse(&rendered(config, &file.content))
+        .targets
+        .into_iter()
+        .filter(|t| !t.is_alias())
+        .map(|t| t.name)
+        .collect()
+}
+
+/// Stacks whose `.mk` is already in the target, with their `<STACK>_DIR`
+pub(super) fn installed_stacks(target: &Path) -> Vec<(String, String)> {
+    AVAILABLE_STACKS
+        .iter()
+        .filter_map(|id| {
+            let content = fs::read_to_string(target.join(format!("{}.mk", id))).ok()?;
+            let dir_var = format!("{}_DIR", id.to_uppercase());
+            let dir = MakeFragment::parse(&content)
+                .variables
+                .into_iter()
+                .find(|v| v.name == dir_var)
+                .map(|v| v.value)
+                .unwrap_or_else(|| ".".to_string());
+            Some((id.to_string(), dir))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_stack_selections() {
+        // Empty target, so only the selection itself is checked
+        let target = std::env::temp_dir().join("devkit-compat-tests-missing");
+        let target = target.to_str().unwrap();
+
+        let cases: [(&[&str], Option<&str>); 6] = [
+            (&["vue@web", "express@api"], None),
+            (&["vue@web", "nuxt@site"], None),
+            (
+                &["vue", "nuxt"],
+                Some("vue and nuxt cannot share '.' (one node-base stack per directory)"),
+            ),
+            (
+                &["rust", "wasm"],
+                Some("rust and wasm cannot share '.' (one wasm stack per directory)"),
+            ),
+            (
+                &["fastapi@api", "django@api"],
+                Some("fastapi and django cannot share 'api' (one python-base stack per directory)"),
+            ),
+            (
+                &["vue@web", "vue@admin"],
+                Some("vue is selected more than once"),
+            ),
+        ];
+
+        for (specs, expected) in cases {
+            let specs: Vec<String> = specs.iter().map(|s| s.to_string()).collect();
+            let result = check_stacks(&specs, target);
+            match expected {
+                None => assert!(result.is_ok(), "{:?}: {:?}", specs, result),
+                Some(message) => {
+                    let error = result.expect_err(&format!("{:?} should fail", specs));
+                    assert!(
+                        error.to_string().contains(message),
+                        "{:?}: {}",
+                        specs,
+                        error
+                    );
+                }
+            }
+        }
+    }
+}
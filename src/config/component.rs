@@ -0,0 +1,315 @@
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use super::{
+    append_missing_lines, embedded, registry, scaffold, split_stack_spec, template, write_file,
+    FileTemplate, StackSpec, Variables, AVAILABLE_STACKS,
+};
+use crate::report::{NextStep, ReportEvent, Reporter};
+
+/// Cross-cutting tooling layered on top of the selected stacks
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Component {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// Stacks or bases the component works with, empty for any stack
+    pub compatible: Vec<String>,
+    pub files: Vec<FileTemplate>,
+    /// Files that belong next to the package manifest, written to the
+    /// `component_dir` of the first supported stack instead of the root
+    pub package_files: Vec<String>,
+    pub next_steps: Vec<NextStep>,
+}
+
+pub const AVAILABLE_COMPONENTS: [&str; 5] = [
+    "editorconfig",
+    "gitignore",
+    "renovate",
+    "husky",
+    "pre-commit",
+];
+
+impl Component {
+    /// True when `stack` or one of the stacks it extends is listed as compatible
+    pub fn supports(&self, stack: &str) -> Result<bool> {
+        if self.compatible.is_empty() {
+            return Ok(true);
+        }
+        let (stack, _) = split_stack_spec(stack);
+        Ok(registry::lineage(stack)?
+            .iter()
+            .any(|s| self.compatible.contains(s)))
+    }
+
+    /// True when `supports` holds for one of `stacks`
+    fn supports_any(&self, stacks: &[String]) -> Result<bool> {
+        for stack in stacks {
+            if self.supports(stack)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Selectable stacks the component works with
+    pub fn supported_stacks(&self) -> Result<Vec<&'static str>> {
+        let mut supported = Vec::new();
+        for stack in AVAILABLE_STACKS {
+            if self.supports(stack)? {
+                supported.push(stack);
+            }
+        }
+        Ok(supported)
+    }
+
+    /// Directory of the supported stacks the component is installed into: the
+    /// root when a supported stack lives there, else the first one's `@dir`
+    pub fn dir(&self, stacks: &[String]) -> Result<String> {
+        if self.compatible.is_empty() {
+            return Ok(".".to_string());
+        }
+        let mut dirs = Vec::new();
+        for stack in stacks {
+            if self.supports(stack)? {
+                dirs.push(StackSpec::parse(stack).dir());
+            }
+        }
+        if dirs.is_empty() || dirs.contains(&".") {
+            return Ok(".".to_string());
+        }
+        Ok(dirs[0].to_string())
+    }
+
+    /// `<id>.mk` when the component ships make targets
+    pub fn makefile(&self) -> Option<String> {
+        let name = format!("{}.mk", self.id);
+        self.files.iter().any(|f| f.path == name).then_some(name)
+    }
+}
+
+/// Components usable with at least one of the selected stacks
+pub fn get_compatible_components(stacks: &[String]) -> Result<Vec<String>> {
+    let mut compatible = Vec::new();
+    for id in AVAILABLE_COMPONENTS {
+        if get_component(id)?.supports_any(stacks)? {
+            compatible.push(id.to_string());
+        }
+    }
+    Ok(compatible)
+}
+
+/// Fail before anything is written when a component fits none of the stacks
+pub fn check_components(components: &[String], stacks: &[String]) -> Result<()> {
+    for id in components {
+        let component = get_component(id)?;
+        if !component.supports_any(stacks)? {
+            return Err(anyhow!(
+                "Component '{}' is not compatible with {} (requires one of: {})",
+                id,
+                stacks.join(", "),
+                component.supported_stacks()?.join(", ")
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub fn apply_component(
+    id: &str,
+    stacks: &[String],
+    target_path: &str,
+    variables: &Variables,
+    reporter: &mut dyn Reporter,
+) -> Result<()> {
+    let component = get_component(id)?;
+    let base_path = PathBuf::from(target_path);
+    let vars = component_variables(&component, stacks, &base_path, variables)?;
+    let dir = base_path.join(&vars["component_dir"]);
+
+    reporter.report(ReportEvent::ComponentStarted {
+        component: component.name.clone(),
+    });
+
+    for file in &component.files {
+        let path = if component.package_files.contains(&file.path) {
+            dir.join(&file.path)
+        } else {
+            base_path.join(&file.path)
+        };
+        let content = template::render(&file.content, &vars);
+        // Project-owned files are extended or kept, never replaced
+        match file.path.as_str() {
+            ".gitignore" => append_missing_lines(&path, &content, reporter),
+            ".editorconfig" if path.exists() => {
+                reporter.report(ReportEvent::FileSkipped {
+                    path: path.display().to_string(),
+                    reason: "already exists".to_string(),
+                });
+                Ok(())
+            }
+            _ => write_file(&path, &content, reporter),
+        }
+        .context(format!("Error creating {}", file.path))?;
+    }
+
+    if !component.next_steps.is_empty() {
+        reporter.report(ReportEvent::NextSteps {
+            steps: component.next_steps,
+        });
+    }
+    Ok(())
+}
+
+/// Overrides plus the `project_name` and `component_dir` builtins, components
+/// declare no variables
+pub(super) fn component_variables(
+    component: &Component,
+    stacks: &[String],
+    base_path: &Path,
+    variables: &Variables,
+) -> Result<Variables> {
+    let builtins = Variables::from([
+        (
+            "project_name".to_string(),
+            scaffold::project_name(base_path),
+        ),
+        ("component_dir".to_string(), component.dir(stacks)?),
+    ]);
+    template::resolve_variables(&[], variables, builtins)
+}
+
+pub fn get_component(id: &str) -> Result<Component> {
+    let component = match id {
+        "editorconfig" => Component {
+            id: id.to_string(),
+            name: "EditorConfig".to_string(),
+            description: "Shared indentation and line ending settings".to_string(),
+            files: vec![FileTemplate {
+                path: ".editorconfig".to_string(),
+                content: embedded::load("components/editorconfig/.editorconfig")?,
+            }],
+            ..Default::default()
+        },
+        "gitignore" => Component {
+            id: id.to_string(),
+            name: ".gitignore".to_string(),
+            description: "Ignore dependencies, build output, caches and secrets".to_string(),
+            files: vec![FileTemplate {
+                path: ".gitignore".to_string(),
+                content: embedded::load("components/gitignore/.gitignore")?,
+            }],
+            ..Default::default()
+        },
+        "renovate" => Component {
+            id: id.to_string(),
+            name: "Renovate".to_string(),
+            description: "Weekly grouped dependency and Docker image updates".to_string(),
+            files: vec![FileTemplate {
+                path: "renovate.json".to_string(),
+                content: embedded::load("components/renovate/renovate.json")?,
+            }],
+            next_steps: vec![NextStep::new(
+                "renovate.json",
+                "Enable the Renovate app on the repository",
+            )],
+            ..Default::default()
+        },
+        "husky" => Component {
+            id: id.to_string(),
+            name: "Husky + commitlint".to_string(),
+            description: "Git hooks running lint-staged and conventional commit checks".to_string(),
+            compatible: vec!["node-base".to_string()],
+            files: vec![
+                FileTemplate {
+                    path: "husky.mk".to_string(),
+                    content: embedded::load("components/husky/husky.mk")?,
+                },
+                FileTemplate {
+                    path: ".husky/pre-commit".to_string(),
+                    content: embedded::load("components/husky/.husky/pre-commit")?,
+                },
+                FileTemplate {
+                    path: ".husky/commit-msg".to_string(),
+                    content: embedded::load("components/husky/.husky/commit-msg")?,
+                },
+                FileTemplate {
+                    path: "commitlint.config.js".to_string(),
+                    content: embedded::load("components/husky/commitlint.config.js")?,
+                },
+                FileTemplate {
+                    path: ".lintstagedrc.json".to_string(),
+                    content: embedded::load("components/husky/.lintstagedrc.json")?,
+                },
+            ],
+            package_files: vec![
+                "commitlint.config.js".to_string(),
+                ".lintstagedrc.json".to_string(),
+            ],
+            next_steps: vec![NextStep::new(
+                "make setup-husky",
+                "Install husky, lint-staged and commitlint",
+            )],
+        },
+        "pre-commit" => Component {
+            id: id.to_string(),
+            name: "pre-commit + ruff".to_string(),
+            description: "pre-commit hooks running ruff check and format".to_string(),
+            compatible: vec!["python-base".to_string()],
+            files: vec![
+                FileTemplate {
+                    path: "pre-commit.mk".to_string(),
+                    content: embedded::load("components/pre-commit/pre-commit.mk")?,
+                },
+                FileTemplate {
+                    path: ".pre-commit-config.yaml".to_string(),
+                    content: embedded::load("components/pre-commit/.pre-commit-config.yaml")?,
+                },
+            ],
+            next_steps: vec![NextStep::new(
+                "make setup-pre-commit",
+                "Install the pre-commit git hooks",
+            )],
+            ..Default::default()
+        },
+        _ => return Err(anyhow!("Component '{}' not recognized", id)),
+    };
+    Ok(component)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_component_without_a_supported_stack() {
+        let stacks = vec!["django".to_string(), "go@svc".to_string()];
+        let error = check_components(&["husky".to_string()], &stacks)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            error,
+            "Component 'husky' is not compatible with django, go@svc \
+             (requires one of: vue, nuxt, react, next, express, nestjs)"
+        );
+    }
+
+    #[test]
+    fn accepts_component_supported_by_any_stack() {
+        let stacks = vec!["django".to_string(), "vue@web".to_string()];
+        let components = vec!["husky".to_string(), "pre-commit".to_string()];
+        assert!(check_components(&components, &stacks).is_ok());
+        assert!(check_components(&["gitignore".to_string()], &stacks[..1]).is_ok());
+    }
+
+    #[test]
+    fn installs_package_files_next_to_the_js_stack() {
+        let husky = get_component("husky").unwrap();
+        let specs = |stacks: &[&str]| stacks.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(husky.dir(&specs(&["django", "vue@web"])).unwrap(), "web");
+        assert_eq!(husky.dir(&specs(&["express@api", "vue"])).unwrap(), ".");
+        let gitignore = get_component("gitignore").unwrap();
+        assert_eq!(gitignore.dir(&specs(&["vue@web"])).unwrap(), ".");
+    }
+}
@@ -0,0 +1,227 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+use super::k8s::{container_port, runtime_stage};
+use super::proxy::{self, Proxy, Upstream};
+use super::{
+    append_missing_lines, load_stack, stack_variables, template, write_file, StackConfig,
+    StackSpec, Variables,
+};
+use crate::report::Reporter;
+
+/// Services of `compose.yaml`, before the proxy is added
+struct Services {
+    yaml: String,
+    upstreams: Vec<Upstream>,
+    /// Prefixed build arguments of stacks in a subdirectory, by stack
+    root_env: Vec<String>,
+}
+
+/// Generate `compose.yaml` with one service per stack, used by the `.mk`
+/// targets when `DOCKER=true`. Each service reads the `.env` of its stack.
+/// Build arguments of stacks in a subdirectory are read from the root `.env`
+/// with a `<STACK>_` prefix, listed in the root `.env.example`.
+/// With a frontend and a backend selected, `proxy` adds a single entrypoint.
+pub fn generate_compose(
+    stacks: &[String],
+    target_path: &str,
+    variables: &Variables,
+    hardened: bool,
+    proxy: Option<Proxy>,
+    reporter: &mut dyn Reporter,
+) -> Result<()> {
+    let base_path = PathBuf::from(target_path);
+    let Services {
+        mut yaml,
+        upstreams,
+        root_env,
+    } = render_services(stacks, &base_path, variables, hardened)?;
+
+    if let Some(proxy) = proxy {
+        if let Some(service) = proxy::generate_proxy(proxy, &upstreams, &base_path, reporter)? {
+            yaml.push_str(&service);
+        }
+    }
+
+    write_file(&base_path.join("compose.yaml"), &yaml, reporter)
+        .context("Error creating compose.yaml")?;
+    if !root_env.is_empty() {
+        append_missing_lines(
+            &base_path.join(".env.example"),
+            &root_env.join("\n\n"),
+            reporter,
+        )
+        .context("Error updating .env.example")?;
+    }
+    Ok(())
+}
+
+fn render_services(
+    stacks: &[String],
+    base_path: &Path,
+    variables: &Variables,
+    hardened: bool,
+) -> Result<Services> {
+    let mut yaml = String::from(
+        "# Generated by devkit; used by make when DOCKER=true\n\
+         # Build arguments default to the values in .env.example\n\
+         services:\n",
+    );
+    let mut upstreams = Vec::new();
+    let mut root_env = Vec::new();
+
+    for stack in stacks {
+        let mut config = load_stack(stack)?;
+        if hardened {
+            config = config.with_hardening()?;
+        }
+        let spec = StackSpec::parse(stack);
+        let vars = stack_variables(&config, &spec, base_path, variables)?;
+        let dockerfile = config
+            .files
+            .iter()
+            .find(|f| f.path == "Dockerfile")
+            .map(|f| f.content.as_str())
+            .unwrap_or_default();
+        let env_file = match spec.dir() {
+            "." => ".env".to_string(),
+            dir => format!("{}/.env", dir),
+        };
+
+        yaml.push_str(&format!(
+            "  {}:\n    build:\n      context: {}\n",
+            config.id,
+            spec.dir()
+        ));
+        let dev = dockerfile.contains(" AS dev\n");
+        if dev {
+            yaml.push_str("      target: dev\n");
+        }
+
+        // The prod stage may pin its own `ARG NAME=<value>` (the unprivileged
+        // nginx port): a build argument would override it
+        let rendered = template::render(dockerfile, &vars);
+        let stage = (!dev).then(|| runtime_stage(&rendered));
+        let args: Vec<_> = build_args(&config, dockerfile, &vars)
+            .into_iter()
+            .filter(|(name, _)| {
+                stage.is_none_or(|stage| !stage.contains(&format!("\nARG {}=", name)))
+            })
+            .collect();
+        if !args.is_empty() {
+            let prefix = &vars["env_prefix"];
+            yaml.push_str("      args:\n");
+            let mut block = format!(
+                "# {} in {}: docker compose build arguments",
+                config.name,
+                spec.dir()
+            );
+            for (name, value) in args {
+                yaml.push_str(&format!(
+                    "        {}: ${{{}{}:-{}}}\n",
+                    name, prefix, name, value
+                ));
+                block.push_str(&format!("\n{}{}={}", prefix, name, value));
+            }
+            if !prefix.is_empty() {
+                root_env.push(block);
+            }
+        }
+
+        yaml.push_str(&format!(
+            "    env_file:\n      - path: {}\n        required: false\n",
+            env_file
+        ));
+        let port = match stage {
+            Some(stage) => container_port(stage, &vars).map(|p| p.to_string()),
+            None => vars.get("app_port").cloned(),
+        };
+        if let Some(port) = port {
+            // Stacks serving on a privileged port publish it on `host_port`
+            let host_port = vars.get("host_port").unwrap_or(&port).clone();
+            yaml.push_str(&format!("    ports:\n      - \"{}:{}\"\n", host_port, port));
+            upstreams.push(Upstream {
+                id: config.id.clone(),
+                port,
+                host_port,
+                api: config.api,
+            });
+        }
+    }
+
+    Ok(Services {
+        yaml,
+        upstreams,
+        root_env,
+    })
+}
+
+/// Global `ARG NAME={{name}}` of a Dockerfile template, with their resolved values
+pub(super) fn build_args<'a>(
+    config: &StackConfig,
+    dockerfile: &str,
+    vars: &'a Variables,
+) -> Vec<(String, &'a String)> {
+    config
+        .variables
+        .iter()
+        .filter(|v| {
+            dockerfile.contains(&format!("ARG {}={{{{{}}}}}", v.name.to_uppercase(), v.name))
+        })
+        .map(|v| (v.name.to_uppercase(), &vars[&v.name]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn services(stacks: &[&str], hardened: bool) -> Services {
+        let stacks: Vec<String> = stacks.iter().map(|s| s.to_string()).collect();
+        render_services(&stacks, Path::new("demo"), &Variables::new(), hardened).unwrap()
+    }
+
+    #[test]
+    fn publishes_wasm_on_an_unprivileged_host_port() {
+        let yaml = services(&["wasm"], false).yaml;
+        assert!(yaml.contains("        APP_PORT: ${APP_PORT:-80}\n"));
+        assert!(yaml.contains("      - \"8080:80\"\n"));
+    }
+
+    #[test]
+    fn hardened_wasm_keeps_the_unprivileged_nginx_port() {
+        let Services {
+            yaml, upstreams, ..
+        } = services(&["wasm"], true);
+        assert!(!yaml.contains("APP_PORT"), "{}", yaml);
+        assert!(yaml.contains("      - \"8080:8080\"\n"));
+        assert_eq!(upstreams[0].port, "8080");
+    }
+
+    #[test]
+    fn hardened_vite_still_runs_the_dev_stage() {
+        for hardened in [false, true] {
+            let yaml = services(&["vue"], hardened).yaml;
+            assert!(yaml.contains("      target: dev\n"));
+            assert!(yaml.contains("        APP_PORT: ${APP_PORT:-5173}\n"));
+            assert!(yaml.contains("      - \"5173:5173\"\n"));
+        }
+    }
+
+    #[test]
+    fn prefixes_build_arguments_of_stacks_in_a_subdirectory() {
+        let Services {
+            yaml,
+            upstreams,
+            root_env,
+        } = services(&["vue", "express@api"], false);
+        assert!(yaml.contains("      context: api\n"));
+        assert!(yaml.contains("        APP_PORT: ${EXPRESS_APP_PORT:-3000}\n"));
+        assert!(yaml.contains("      - path: api/.env\n"));
+        assert_eq!(root_env.len(), 1);
+        assert!(root_env[0].starts_with("# Express in api: "));
+        assert!(root_env[0].contains("\nEXPRESS_APP_PORT=3000"));
+        let api: Vec<_> = upstreams.iter().map(|u| (u.id.as_str(), u.api)).collect();
+        assert_eq!(api, [("vue", false), ("express", true)]);
+    }
+}
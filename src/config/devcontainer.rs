@@ -0,0 +1,162 @@
+use anyhow::{Context, Result};
+use serde_json::{json, Map, Value};
+use std::path::{Path, PathBuf};
+
+use super::compat::make_recipes;
+use super::compose::build_args;
+use super::{load_stack, stack_variables, template, write_file, StackConfig, StackSpec, Variables};
+use crate::report::{ReportEvent, Reporter};
+
+/// Generate `.devcontainer/devcontainer.json` building the `dev` stage of the
+/// stack Dockerfile (or its toolchain image), or one
+/// `.devcontainer/<stack>/devcontainer.json` per stack when several are selected
+pub fn generate_devcontainer(
+    stacks: &[String],
+    target_path: &str,
+    variables: &Variables,
+    reporter: &mut dyn Reporter,
+) -> Result<()> {
+    let base_path = PathBuf::from(target_path);
+    let single = stacks.len() == 1;
+
+    for stack in stacks {
+        let config = load_stack(stack)?;
+        let spec = StackSpec::parse(stack);
+        let Some(devcontainer) =
+            render_devcontainer(&config, &spec, single, &base_path, variables)?
+        else {
+            reporter.report(ReportEvent::Warning {
+                message: format!(
+                    "{} has no dev stage or toolchain image, no devcontainer generated",
+                    config.name
+                ),
+            });
+            continue;
+        };
+
+        let dir = if single {
+            PathBuf::from(".devcontainer")
+        } else {
+            PathBuf::from(".devcontainer").join(&config.id)
+        };
+        let path = base_path.join(dir).join("devcontainer.json");
+        let json = serde_json::to_string_pretty(&devcontainer)?;
+        write_file(&path, &format!("{}\n", json), reporter)
+            .with_context(|| format!("Error creating {}", path.display()))?;
+    }
+
+    Ok(())
+}
+
+/// Content of one `devcontainer.json`, `None` when the stack has neither a
+/// `dev` stage nor a toolchain image
+fn render_devcontainer(
+    config: &StackConfig,
+    spec: &StackSpec,
+    single: bool,
+    base_path: &Path,
+    variables: &Variables,
+) -> Result<Option<Value>> {
+    let vars = stack_variables(config, spec, base_path, variables)?;
+    let dockerfile = config
+        .files
+        .iter()
+        .find(|f| f.path == "Dockerfile")
+        .map(|f| f.content.as_str())
+        .unwrap_or_default();
+
+    // Paths are relative to devcontainer.json
+    let root = if single { ".." } else { "../.." };
+    let context = match spec.dir() {
+        "." => root.to_string(),
+        stack_dir => format!("{}/{}", root, stack_dir),
+    };
+
+    let mut fields = Map::new();
+    fields.insert(
+        "name".to_string(),
+        json!(format!("{} ({})", vars["project_name"], config.name)),
+    );
+    if dockerfile.contains(" AS dev\n") {
+        let mut build = json!({
+            "dockerfile": format!("{}/Dockerfile", context),
+            "context": context,
+            "target": "dev",
+        });
+        let args = build_args(config, dockerfile, &vars);
+        if !args.is_empty() {
+            build["args"] = args
+                .into_iter()
+                .map(|(name, value)| (name, json!(value)))
+                .collect::<Map<_, _>>()
+                .into();
+        }
+        fields.insert("build".to_string(), build);
+    } else if let Some(image) = &config.dev_image {
+        fields.insert("image".to_string(), json!(template::render(image, &vars)));
+    } else {
+        return Ok(None);
+    }
+
+    // The dev server port, which differs from app_port when prod is served by nginx
+    let port = vars.get("dev_port").or_else(|| vars.get("app_port"));
+    if let Some(port) = port.and_then(|p| p.parse::<u16>().ok()) {
+        fields.insert("forwardPorts".to_string(), json!([port]));
+    }
+    let install = format!("install-{}", config.id);
+    if make_recipes(config).contains(&install) {
+        fields.insert(
+            "postCreateCommand".to_string(),
+            json!(format!("make {}", install)),
+        );
+    }
+    if !config.editor_extensions.is_empty() {
+        fields.insert(
+            "customizations".to_string(),
+            json!({ "vscode": { "extensions": config.editor_extensions } }),
+        );
+    }
+    Ok(Some(Value::Object(fields)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn devcontainer(stack: &str, single: bool) -> Value {
+        let config = load_stack(stack).unwrap();
+        let spec = StackSpec::parse(stack);
+        render_devcontainer(&config, &spec, single, Path::new("demo"), &Variables::new())
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn builds_the_dev_stage_of_a_single_root_stack() {
+        let json = devcontainer("fastapi", true);
+        assert_eq!(json["name"], "demo (FastAPI)");
+        assert_eq!(json["build"]["dockerfile"], "../Dockerfile");
+        assert_eq!(json["build"]["context"], "..");
+        assert_eq!(json["build"]["target"], "dev");
+        assert_eq!(json["build"]["args"]["APP_PORT"], "8000");
+        assert_eq!(json["forwardPorts"], json!([8000]));
+        assert_eq!(json["postCreateCommand"], "make install-fastapi");
+    }
+
+    #[test]
+    fn points_at_the_stack_directory_when_several_stacks_are_selected() {
+        let json = devcontainer("vue@web", false);
+        assert_eq!(json["build"]["dockerfile"], "../../web/Dockerfile");
+        assert_eq!(json["build"]["context"], "../../web");
+        assert_eq!(json["build"]["args"]["APP_PORT"], "5173");
+        assert_eq!(json["forwardPorts"], json!([5173]));
+    }
+
+    #[test]
+    fn forwards_the_wasm_dev_server_port_rather_than_nginx() {
+        let json = devcontainer("wasm", true);
+        assert!(json.get("build").is_none());
+        assert_eq!(json["image"], "docker.io/rust:1.90.0");
+        assert_eq!(json["forwardPorts"], json!([8080]));
+    }
+}
@@ -0,0 +1,159 @@
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::report::{NextStep, ReportEvent, Reporter};
+
+use super::write_file;
+
+/// Pair every template path with its `include_str!` copy
+macro_rules! embed {
+    ($($path:literal),* $(,)?) => {
+        &[$(($path, include_str!(concat!("../../templates/", $path)))),*]
+    };
+}
+
+/// Every template compiled into the binary, keyed by its path under `templates/`
+pub const TEMPLATES: &[(&str, &str)] = embed![
+    "components/editorconfig/.editorconfig",
+    "components/gitignore/.gitignore",
+    "components/husky/.husky/commit-msg",
+    "components/husky/.husky/pre-commit",
+    "components/husky/.lintstagedrc.json",
+    "components/husky/commitlint.config.js",
+    "components/husky/husky.mk",
+    "components/pre-commit/.pre-commit-config.yaml",
+    "components/pre-commit/pre-commit.mk",
+    "components/renovate/renovate.json",
+    "django/django.mk",
+    "express/express.mk",
+    "fastapi/scaffold/main.py",
+    "fastapi/scaffold/pyproject.toml",
+    "flask/flask.mk",
+    "go/.dockerignore",
+    "go/.env.example",
+    "go/.tool-versions",
+    "go/Dockerfile",
+    "go/go.mk",
+    "makefiles/common.mk",
+    "nestjs/nestjs.mk",
+    "next/Dockerfile",
+    "next/hardened.Dockerfile",
+    "next/next.mk",
+    "nginx/nginx.conf",
+    "node/.dockerignore",
+    "node/.env.example",
+    "node/.node-version",
+    "node/.nvmrc",
+    "node/.tool-versions",
+    "node/Dockerfile",
+    "node/node.mk",
+    "nuxt/Dockerfile",
+    "nuxt/Dockerfile.static",
+    "nuxt/hardened.Dockerfile",
+    "nuxt/scaffold/app.vue",
+    "nuxt/scaffold/nuxt.config.ts",
+    "nuxt/scaffold/package.json",
+    "nuxt/static.hardened.Dockerfile",
+    "proxy/Caddyfile",
+    "proxy/traefik.yml",
+    "python/.dockerignore",
+    "python/.env.example",
+    "python/.python-version",
+    "python/.tool-versions",
+    "python/Dockerfile",
+    "python/hardened.Dockerfile",
+    "python/python.mk",
+    "rust/.dockerignore",
+    "rust/.env.example",
+    "rust/.tool-versions",
+    "rust/Dockerfile",
+    "rust/rust-toolchain.toml",
+    "rust/rust.mk",
+    "vite/.env.example",
+    "vite/.tool-versions",
+    "vite/Dockerfile",
+    "vite/hardened.Dockerfile",
+    "vue/caddy.Dockerfile",
+    "vue/caddy.hardened.Dockerfile",
+    "vue/scaffold/index.html",
+    "vue/scaffold/package.json",
+    "vue/scaffold/src/App.vue",
+    "vue/scaffold/src/env.d.ts",
+    "vue/scaffold/src/main.ts",
+    "vue/scaffold/vite.config.ts",
+    "wasm/.dockerignore",
+    "wasm/.env.example",
+    "wasm/.tool-versions",
+    "wasm/Dockerfile",
+    "wasm/hardened.Dockerfile",
+    "wasm/rust-toolchain.toml",
+    "wasm/wasm.mk",
+];
+
+/// Directory read before the embedded copies (`--templates` or `DEVKIT_TEMPLATES`)
+static TEMPLATE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Read templates from `dir` first; files missing there keep their embedded copy
+pub fn set_template_dir(dir: PathBuf) -> Result<()> {
+    if !dir.is_dir() {
+        return Err(anyhow!(
+            "Template directory {} does not exist",
+            dir.display()
+        ));
+    }
+    TEMPLATE_DIR
+        .set(dir)
+        .map_err(|_| anyhow!("Template directory is already set"))
+}
+
+/// Content of `templates/<path>`, from the template directory when it has the file
+pub(super) fn load(path: &str) -> Result<String> {
+    if let Some(dir) = TEMPLATE_DIR.get() {
+        let file = dir.join(path);
+        match fs::read_to_string(&file) {
+            Ok(content) => return Ok(content),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e).with_context(|| format!("Error reading {}", file.display())),
+        }
+    }
+    TEMPLATES
+        .iter()
+        .find(|(p, _)| *p == path)
+        .map(|(_, content)| content.to_string())
+        .ok_or_else(|| anyhow!("Template {} is not embedded", path))
+}
+
+/// Write every embedded template under `dir`, ready to be edited and passed
+/// back with `--templates`
+pub fn export_templates(dir: &str, reporter: &mut dyn Reporter) -> Result<()> {
+    let base_path = Path::new(dir);
+    for (path, content) in TEMPLATES {
+        write_file(&base_path.join(path), content, reporter)
+            .context(format!("Error exporting {}", path))?;
+    }
+    reporter.report(ReportEvent::NextSteps {
+        steps: vec![NextStep::new(
+            format!("devkit --templates {} config <stack>", dir),
+            "Configure a project from the exported templates",
+        )],
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_template_ends_with_a_newline() {
+        let missing: Vec<_> = TEMPLATES
+            .iter()
+            .filter(|(_, content)| !content.ends_with('\n'))
+            .map(|(path, _)| *path)
+            .collect();
+        assert!(missing.is_empty(), "no trailing newline: {:?}", missing);
+    }
+}
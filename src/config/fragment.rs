@@ -0,0 +1,353 @@
+use serde::{Deserialize, Serialize};
+
+/// Parsed `.mk` template, merged target by target along the `extends` chain
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MakeFragment {
+    pub variables: Vec<MakeVariable>,
+    /// Names listed on `.PHONY:` lines, rendered as a single rule
+    pub phony: Vec<String>,
+    pub targets: Vec<MakeTarget>,
+}
+
+/// `NAME ?= value` default
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MakeVariable {
+    pub name: String,
+    pub value: String,
+    pub comments: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MakeTarget {
+    pub name: String,
+    pub prerequisites: String,
+    pub description: Option<String>,
+    pub recipe: Vec<String>,
+    pub comments: Vec<String>,
+    /// Target-specific assignments such as `APP_PORT := $(API_APP_PORT)`
+    pub scoped: Vec<String>,
+}
+
+impl MakeTarget {
+    /// Prerequisite-only rule such as `dev: dev-vue`
+    pub fn is_alias(&self) -> bool {
+        self.recipe.is_empty()
+    }
+}
+
+impl MakeFragment {
+    pub fn parse(source: &str) -> Self {
+        let mut fragment = MakeFragment::default();
+        let mut comments: Vec<String> = Vec::new();
+        let lines: Vec<&str> = source.lines().collect();
+        let mut i = 0;
+
+        while i < lines.len() {
+            let line = lines[i];
+            i += 1;
+
+            if line.trim().is_empty() {
+                comments.clear();
+                continue;
+            }
+            if line.starts_with('#') {
+                comments.push(line.to_string());
+                continue;
+            }
+
+            if let Some((name, value)) = line.split_once("?=") {
+                fragment.variables.push(MakeVariable {
+                    name: name.trim().to_string(),
+                    value: value.trim().to_string(),
+                    comments: std::mem::take(&mut comments),
+                });
+                continue;
+            }
+
+            if let Some(names) = line.strip_prefix(".PHONY:") {
+                for name in names.split_whitespace() {
+                    if !fragment.phony.iter().any(|p| p == name) {
+                        fragment.phony.push(name.to_string());
+                    }
+                }
+                comments.clear();
+                continue;
+            }
+
+            let Some((name, rest)) = line.split_once(':') else {
+                comments.clear();
+                continue;
+            };
+            // `target: VAR := value` belongs to the rule of the same name
+            if rest.contains('=') && !rest.contains("##") {
+                let assignment = rest.trim().to_string();
+                let name = name.trim();
+                match fragment.targets.iter_mut().find(|t| t.name == name) {
+                    Some(target) => target.scoped.push(assignment),
+                    None => fragment.targets.push(MakeTarget {
+                        name: name.to_string(),
+                        prerequisites: String::new(),
+                        description: None,
+                        recipe: Vec::new(),
+                        comments: std::mem::take(&mut comments),
+                        scoped: vec![assignment],
+                    }),
+                }
+                continue;
+            }
+
+            let (prerequisites, description) = match rest.split_once("##") {
+                Some((prereqs, desc)) => (prereqs, Some(desc.trim().to_string())),
+                None => (rest, None),
+            };
+
+            // Recipe lines start with a tab; blank lines inside a recipe are kept
+            let mut recipe = Vec::new();
+            while i < lines.len() {
+                if lines[i].starts_with('\t') {
+                    recipe.push(lines[i].to_string());
+                    i += 1;
+                } else if lines[i].trim().is_empty()
+                    && lines.get(i + 1).is_some_and(|next| next.starts_with('\t'))
+                {
+                    recipe.push(String::new());
+                    i += 1;
+                } else {
+                    break;
+                }
+            }
+
+            let target = MakeTarget {
+                name: name.trim().to_string(),
+                prerequisites: prerequisites.trim().to_string(),
+                description,
+                recipe,
+                comments: std::mem::take(&mut comments),
+                scoped: Vec::new(),
+            };
+            // A rule following its target-specific assignments completes them
+            match fragment
+                .targets
+                .iter_mut()
+                .find(|t| t.name == target.name && t.is_alias() && t.prerequisites.is_empty())
+            {
+                Some(existing) => {
+                    let scoped = std::mem::take(&mut existing.scoped);
+                    let mut comments = std::mem::take(&mut existing.comments);
+                    comments.extend(target.comments.iter().cloned());
+                    *existing = MakeTarget {
+                        scoped,
+                        comments,
+                        ..target
+                    };
+                }
+                None => fragment.targets.push(target),
+            }
+        }
+
+        fragment
+    }
+
+    /// Overlay `child` on top of this fragment: same-named variables and targets
+    /// are replaced in place, new ones are appended; `.PHONY` names are unioned
+    pub fn merge(&mut self, child: &MakeFragment) {
+        for name in &child.phony {
+            if !self.phony.contains(name) {
+                self.phony.push(name.clone());
+            }
+        }
+
+        for var in &child.variables {
+            match self.variables.iter_mut().find(|v| v.name == var.name) {
+                Some(existing) => *existing = var.clone(),
+                None => self.variables.push(var.clone()),
+            }
+        }
+
+        for target in &child.targets {
+            match self.targets.iter_mut().find(|t| t.name == target.name) {
+                Some(existing) => *existing = target.clone(),
+                None => self.targets.push(target.clone()),
+            }
+        }
+    }
+
+    /// Render back to make syntax, aliases last
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        for var in &self.variables {
+            push_comments(&mut out, &var.comments);
+            out.push_str(&format!("{} ?= {}\n", var.name, var.value));
+        }
+
+        if !self.phony.is_empty() {
+            out.push_str(&format!("\n.PHONY: {}\n", self.phony.join(" ")));
+        }
+
+        let (aliases, targets): (Vec<_>, Vec<_>) = self.targets.iter().partition(|t| t.is_alias());
+
+        for target in targets {
+            out.push('\n');
+            push_comments(&mut out, &target.comments);
+            for assignment in &target.scoped {
+                out.push_str(&format!("{}: {}\n", target.name, assignment));
+            }
+            out.push_str(&target_line(target));
+            for line in &target.recipe {
+                out.push_str(line);
+                out.push('\n');
+            }
+        }
+
+        if !aliases.is_empty() {
+            out.push('\n');
+        }
+        for target in aliases {
+            push_comments(&mut out, &target.comments);
+            out.push_str(&target_line(target));
+        }
+
+        out
+    }
+}
+
+fn push_comments(out: &mut String, comments: &[String]) {
+    for comment in comments {
+        out.push_str(comment);
+        out.push('\n');
+    }
+}
+
+fn target_line(target: &MakeTarget) -> String {
+    let mut line = format!("{}:", target.name);
+    if !target.prerequisites.is_empty() {
+        line.push(' ');
+        line.push_str(&target.prerequisites);
+    }
+    if let Some(description) = &target.description {
+        line.push_str(" ## ");
+        line.push_str(description);
+    }
+    line.push('\n');
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = "# Default variables if not defined
+APP_DIR ?= app
+DOCKER ?= false
+
+.PHONY: dev-app build-app
+
+# Start the server
+dev-app: ## Start app
+\t@echo dev
+
+\tnpm run dev
+
+build-app: deps ## Build app
+\tnpm run build
+
+dev: dev-app
+";
+
+    fn names(fragment: &MakeFragment) -> Vec<&str> {
+        fragment.targets.iter().map(|t| t.name.as_str()).collect()
+    }
+
+    #[test]
+    fn parses_variables_targets_and_recipes() {
+        let fragment = MakeFragment::parse(BASE);
+
+        let vars: Vec<_> = fragment
+            .variables
+            .iter()
+            .map(|v| (v.name.as_str(), v.value.as_str()))
+            .collect();
+        assert_eq!(vars, [("APP_DIR", "app"), ("DOCKER", "false")]);
+        assert_eq!(
+            fragment.variables[0].comments,
+            ["# Default variables if not defined"]
+        );
+
+        assert_eq!(names(&fragment), ["dev-app", "build-app", "dev"]);
+        let dev = &fragment.targets[0];
+        assert_eq!(dev.description.as_deref(), Some("Start app"));
+        assert_eq!(dev.comments, ["# Start the server"]);
+        assert_eq!(dev.recipe, ["\t@echo dev", "", "\tnpm run dev"]);
+        assert_eq!(fragment.targets[1].prerequisites, "deps");
+        assert!(fragment.targets[2].is_alias());
+    }
+
+    #[test]
+    fn render_round_trips() {
+        let rendered = MakeFragment::parse(BASE).render();
+        assert_eq!(rendered, BASE);
+        assert_eq!(MakeFragment::parse(&rendered).render(), rendered);
+    }
+
+    #[test]
+    fn child_variables_override_in_place() {
+        let mut fragment = MakeFragment::parse(BASE);
+        fragment.merge(&MakeFragment::parse("EXTRA ?= 1\nAPP_DIR ?= web\n"));
+
+        let vars: Vec<_> = fragment
+            .variables
+            .iter()
+            .map(|v| format!("{} ?= {}", v.name, v.value))
+            .collect();
+        assert_eq!(vars, ["APP_DIR ?= web", "DOCKER ?= false", "EXTRA ?= 1"]);
+    }
+
+    #[test]
+    fn child_targets_replace_and_append_without_duplicates() {
+        let mut fragment = MakeFragment::parse(BASE);
+        fragment.merge(&MakeFragment::parse(
+            "build-app: ## Build app with vite\n\tvite build\n\nlint-app: ## Lint app\n\teslint .\n",
+        ));
+
+        assert_eq!(
+            names(&fragment),
+            ["dev-app", "build-app", "dev", "lint-app"]
+        );
+        let build = &fragment.targets[1];
+        assert_eq!(build.description.as_deref(), Some("Build app with vite"));
+        assert_eq!(build.prerequisites, "");
+        assert_eq!(build.recipe, ["\tvite build"]);
+
+        let rendered = fragment.render();
+        assert_eq!(rendered.matches("build-app:").count(), 1);
+        // Aliases render after recipes
+        assert!(rendered.ends_with("\ndev: dev-app\n"));
+    }
+
+    #[test]
+    fn keeps_target_specific_variables_on_their_rule() {
+        let source = "PORT ?= 80
+
+run: PORT := $(API_PORT)
+run: ## Run
+\tserve $(PORT)
+";
+        let fragment = MakeFragment::parse(source);
+        assert_eq!(names(&fragment), ["run"]);
+        assert_eq!(fragment.targets[0].scoped, ["PORT := $(API_PORT)"]);
+        assert_eq!(fragment.targets[0].recipe, ["\tserve $(PORT)"]);
+        assert_eq!(fragment.render(), source);
+    }
+
+    #[test]
+    fn collects_phony_names_across_lines_and_merges() {
+        let mut fragment = MakeFragment::parse(".PHONY: a b\n.PHONY: b c\n\na:\n\ttrue\n");
+        assert_eq!(fragment.phony, ["a", "b", "c"]);
+        assert_eq!(names(&fragment), ["a"]);
+
+        fragment.merge(&MakeFragment::parse(".PHONY: c d\n"));
+        assert_eq!(fragment.phony, ["a", "b", "c", "d"]);
+        assert!(fragment.render().starts_with("\n.PHONY: a b c d\n"));
+    }
+}
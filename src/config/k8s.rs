@@ -0,0 +1,545 @@
+use anyhow::{anyhow, Context, Result};
+use serde_json::{json, Map, Value};
+use std::path::PathBuf;
+
+use super::{load_stack, scaffold, stack_variables, template, write_file, StackSpec, Variables};
+use crate::report::Reporter;
+
+/// `HEALTHCHECK` of a Dockerfile prod stage
+#[derive(Debug, Default)]
+struct Healthcheck {
+    /// Path requested on localhost, `None` when the command is not an HTTP check
+    path: Option<String>,
+    interval: Option<u64>,
+    timeout: Option<u64>,
+    start_period: Option<u64>,
+    retries: Option<u64>,
+}
+
+/// Last stage of a Dockerfile, the image that gets deployed
+pub(super) fn runtime_stage(dockerfile: &str) -> &str {
+    let start = dockerfile.rfind("\nFROM ").map_or(0, |i| i + 1);
+    &dockerfile[start..]
+}
+
+/// Port the prod stage listens on: its `ARG APP_PORT=<n>` or numeric
+/// `EXPOSE`, otherwise `app_port`
+pub(super) fn container_port(stage: &str, vars: &Variables) -> Option<u16> {
+    stage
+        .lines()
+        .find_map(|line| {
+            line.strip_prefix("ARG APP_PORT=")
+                .or_else(|| line.strip_prefix("EXPOSE "))
+                .and_then(|port| port.trim().parse().ok())
+        })
+        .or_else(|| vars.get("app_port").and_then(|port| port.parse().ok()))
+}
+
+/// Docker duration (`30s`, `1m`) in seconds
+fn seconds(duration: &str) -> Option<u64> {
+    match duration.strip_suffix('m') {
+        Some(minutes) => minutes.parse::<u64>().ok().map(|m| m * 60),
+        None => duration.trim_end_matches('s').parse().ok(),
+    }
+}
+
+fn parse_healthcheck(stage: &str) -> Option<Healthcheck> {
+    let start = stage.find("HEALTHCHECK ")?;
+    let instruction = stage[start..].split("\n\n").next().unwrap_or_default();
+
+    let mut check = Healthcheck::default();
+    for option in instruction.split_whitespace() {
+        let Some((name, value)) = option.split_once('=') else {
+            continue;
+        };
+        match name {
+            "--interval" => check.interval = seconds(value),
+            "--timeout" => check.timeout = seconds(value),
+            "--start-period" => check.start_period = seconds(value),
+            "--retries" => check.retries = value.parse().ok(),
+            _ => {}
+        }
+    }
+    // `http://localhost:${APP_PORT}/health`, `'http://localhost:' + port + '/health'`
+    check.path = instruction.split_once("http://localhost").map(|(_, rest)| {
+        let path = rest
+            .find('/')
+            .map(|i| &rest[i..])
+            .unwrap_or("/")
+            .split(['\'', '"', ' ', ')', ','])
+            .next()
+            .unwrap_or("/");
+        path.to_string()
+    });
+    Some(check)
+}
+
+/// Readiness and liveness probes mirroring the Dockerfile healthcheck, or a
+/// TCP check of the port when the image has none
+fn probes(healthcheck: Option<&Healthcheck>) -> (Value, Value) {
+    let Some(check) = healthcheck else {
+        let tcp = json!({ "tcpSocket": { "port": "http" }, "periodSeconds": 10 });
+        return (tcp.clone(), tcp);
+    };
+
+    let action = match &check.path {
+        Some(path) => json!({ "httpGet": { "path": path, "port": "http" } }),
+        None => json!({ "tcpSocket": { "port": "http" } }),
+    };
+    let mut readiness = action.clone();
+    let mut liveness = action;
+    for (probe, delay) in [(&mut readiness, None), (&mut liveness, check.start_period)] {
+        let probe = probe.as_object_mut().expect("probe is an object");
+        let timings = [
+            ("initialDelaySeconds", delay),
+            ("periodSeconds", check.interval),
+            ("timeoutSeconds", check.timeout),
+            ("failureThreshold", check.retries),
+        ];
+        for (name, value) in timings {
+            if let Some(value) = value {
+                probe.insert(name.to_string(), json!(value));
+            }
+        }
+    }
+    (readiness, liveness)
+}
+
+/// YAML scalar, in plain style only when it is made of characters that can
+/// never turn it into another type or into YAML syntax; anything else is
+/// written as a JSON string, which is a valid double-quoted YAML scalar
+fn yaml_scalar(value: &Value) -> String {
+    let Value::String(text) = value else {
+        return value.to_string();
+    };
+    let plain = text.starts_with(|c: char| c.is_ascii_alphabetic() || c == '/' || c == '_')
+        && text
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "._/:@=+-".contains(c))
+        && !text.ends_with(':')
+        && !matches!(
+            text.to_lowercase().as_str(),
+            "true" | "false" | "yes" | "no" | "y" | "n" | "on" | "off" | "null"
+        );
+    if plain {
+        text.clone()
+    } else {
+        serde_json::to_string(text).unwrap_or_default()
+    }
+}
+
+fn is_collection(value: &Value) -> bool {
+    match value {
+        Value::Object(map) => !map.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        _ => false,
+    }
+}
+
+/// Block-style YAML of a JSON value, nested `indent` spaces deep
+fn to_yaml(value: &Value, indent: usize, out: &mut String) {
+    let pad = " ".repeat(indent);
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                match child {
+                    // Multi-line text such as a config file, as a literal block
+                    Value::String(text) if text.contains('\n') && !text.starts_with(' ') => {
+                        let chomp = if text.ends_with('\n') { "" } else { "-" };
+                        out.push_str(&format!("{}{}: |{}\n", pad, key, chomp));
+                        for line in text.lines() {
+                            match line {
+                                "" => out.push('\n'),
+                                _ => out.push_str(&format!("{}  {}\n", pad, line)),
+                            }
+                        }
+                    }
+                    _ if !is_collection(child) => {
+                        let scalar = match child {
+                            Value::Object(_) => "{}".to_string(),
+                            Value::Array(_) => "[]".to_string(),
+                            _ => yaml_scalar(child),
+                        };
+                        out.push_str(&format!("{}{}: {}\n", pad, key, scalar));
+                    }
+                    _ => {
+                        out.push_str(&format!("{}{}:\n", pad, key));
+                        to_yaml(child, indent + 2, out);
+                    }
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                if is_collection(item) && item.is_object() {
+                    // First key shares the line of the dash
+                    let mut nested = String::new();
+                    to_yaml(item, indent + 2, &mut nested);
+                    out.push_str(&format!("{}- {}", pad, &nested[indent + 2..]));
+                } else {
+                    out.push_str(&format!("{}- {}\n", pad, yaml_scalar(item)));
+                }
+            }
+        }
+        _ => out.push_str(&format!("{}{}\n", pad, yaml_scalar(value))),
+    }
+}
+
+/// Generate `k8s/<stack>.yaml` with a Deployment, a Service and, when
+/// `ingress_host` is set, an Ingress per stack, plus a `kustomization.yaml`.
+/// Images are `<image_registry>/<stack>:<image_tag>`, the tag defaulting to
+/// `latest`. Without `image_registry` the deployments run the local
+/// `<project>-<stack>` images docker compose builds and never pull them.
+/// nginx stacks get their `nginx.conf` from a ConfigMap that proxies `/api`
+/// through the cluster DNS instead of Docker's resolver.
+pub fn generate_k8s(
+    stacks: &[String],
+    target_path: &str,
+    variables: &Variables,
+    hardened: bool,
+    reporter: &mut dyn Reporter,
+) -> Result<()> {
+    let base_path = PathBuf::from(target_path);
+    let project_name = scaffold::project_name(&base_path);
+    let registry = variables.get("image_registry").filter(|r| !r.is_empty());
+    let tag = variables
+        .get("image_tag")
+        .map(String::as_str)
+        .unwrap_or("latest");
+    let host = variables.get("ingress_host").filter(|h| !h.is_empty());
+
+    let mut files = Vec::new();
+    for stack in stacks {
+        let mut config = load_stack(stack)?;
+        if hardened {
+            config = config.with_hardening()?;
+        }
+        let spec = StackSpec::parse(stack);
+        // Service names resolve through the pod's resolv.conf, not 127.0.0.11
+        let mut cluster_vars = variables.clone();
+        cluster_vars.insert("nginx_resolver".to_string(), String::new());
+        let vars = stack_variables(&config, &spec, &base_path, &cluster_vars)?;
+        let dockerfile = config
+            .files
+            .iter()
+            .find(|f| f.path == "Dockerfile")
+            .map(|f| template::render(&f.content, &vars))
+            .unwrap_or_default();
+        let stage = runtime_stage(&dockerfile);
+        let port = container_port(stage, &vars)
+            .ok_or_else(|| anyhow!("{} has no port to expose", config.name))?;
+        let healthcheck = parse_healthcheck(stage);
+        let (readiness, liveness) = probes(healthcheck.as_ref());
+
+        let labels = json!({
+            "app.kubernetes.io/name": config.id,
+            "app.kubernetes.io/part-of": project_name,
+        });
+        let mut container = json!({
+            "name": config.id,
+            "image": match registry {
+                Some(registry) => format!("{}/{}:{}", registry, config.id, tag),
+                None => format!("{}-{}:{}", project_name, config.id, tag),
+            },
+            "ports": [{ "name": "http", "containerPort": port }],
+            "readinessProbe": readiness,
+            "livenessProbe": liveness,
+        });
+        if registry.is_none() {
+            container["imagePullPolicy"] = json!("Never");
+        }
+        let mut pod_spec = Map::new();
+        let mut manifests = Vec::new();
+        if let Some(nginx_conf) = config.files.iter().find(|f| f.path == "nginx.conf") {
+            let name = format!("{}-nginx", config.id);
+            manifests.push((
+                "ConfigMap",
+                json!({
+                    "apiVersion": "v1",
+                    "kind": "ConfigMap",
+                    "metadata": { "name": name, "labels": labels },
+                    "data": { "default.conf": template::render(&nginx_conf.content, &vars) },
+                }),
+            ));
+            container["volumeMounts"] = json!([{
+                "name": "nginx-conf",
+                "mountPath": "/etc/nginx/conf.d/default.conf",
+                "subPath": "default.conf",
+                "readOnly": true,
+            }]);
+            pod_spec.insert(
+                "volumes".to_string(),
+                json!([{ "name": "nginx-conf", "configMap": { "name": name } }]),
+            );
+        }
+        if config.hardened {
+            container["securityContext"] = json!({
+                "runAsNonRoot": true,
+                "allowPrivilegeEscalation": false,
+            });
+        }
+
+        pod_spec.insert("containers".to_string(), json!([container]));
+        manifests.extend([
+            (
+                "Deployment",
+                json!({
+                    "apiVersion": "apps/v1",
+                    "kind": "Deployment",
+                    "metadata": { "name": config.id, "labels": labels },
+                    "spec": {
+                        "replicas": 1,
+                        "selector": { "matchLabels": { "app.kubernetes.io/name": config.id } },
+                        "template": {
+                            "metadata": { "labels": labels },
+                            "spec": pod_spec,
+                        },
+                    },
+                }),
+            ),
+            (
+                "Service",
+                json!({
+                    "apiVersion": "v1",
+                    "kind": "Service",
+                    "metadata": { "name": config.id, "labels": labels },
+                    "spec": {
+                        "type": "ClusterIP",
+                        "selector": { "app.kubernetes.io/name": config.id },
+                        "ports": [{ "name": "http", "port": port, "targetPort": "http" }],
+                    },
+                }),
+            ),
+        ]);
+        if let Some(host) = host {
+            // Backends share the host under /api, like the compose proxy
+            let path = if config.api { "/api" } else { "/" };
+            let mut ingress_spec = Map::new();
+            if let Some(class) = variables.get("ingress_class") {
+                ingress_spec.insert("ingressClassName".to_string(), json!(class));
+            }
+            ingress_spec.insert(
+                "rules".to_string(),
+                json!([{
+                    "host": host,
+                    "http": { "paths": [{
+                        "path": path,
+                        "pathType": "Prefix",
+                        "backend": { "service": { "name": config.id, "port": { "name": "http" } } },
+                    }] },
+                }]),
+            );
+            manifests.push((
+                "Ingress",
+                json!({
+                    "apiVersion": "networking.k8s.io/v1",
+                    "kind": "Ingress",
+                    "metadata": { "name": config.id, "labels": labels },
+                    "spec": ingress_spec,
+                }),
+            ));
+        }
+
+        let mut content = format!(
+            "# Generated by devkit: {} of {}\n",
+            manifests
+                .iter()
+                .map(|(kind, _)| *kind)
+                .collect::<Vec<_>>()
+                .join(", "),
+            config.name
+        );
+        for (index, (_, manifest)) in manifests.iter().enumerate() {
+            if index > 0 {
+                content.push_str("---\n");
+            }
+            to_yaml(manifest, 0, &mut content);
+        }
+        files.push((format!("{}.yaml", config.id), content));
+    }
+
+    let k8s_path = base_path.join("k8s");
+    let mut kustomization = String::from(
+        "# Generated by devkit: kubectl apply -k k8s\n\
+         apiVersion: kustomize.config.k8s.io/v1beta1\n\
+         kind: Kustomization\n\
+         resources:\n",
+    );
+    for (name, content) in &files {
+        kustomization.push_str(&format!("  - {}\n", name));
+        write_file(&k8s_path.join(name), content, reporter)
+            .with_context(|| format!("Error creating k8s/{}", name))?;
+    }
+    write_file(
+        &k8s_path.join("kustomization.yaml"),
+        &kustomization,
+        reporter,
+    )
+    .context("Error creating k8s/kustomization.yaml")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_ambiguous_scalars() {
+        let cases = [
+            ("80", "\"80\""),
+            ("true", "\"true\""),
+            ("", "\"\""),
+            (":x", "\":x\""),
+            ("a: b", "\"a: b\""),
+            ("/health", "/health"),
+            ("acme/api:1.0", "acme/api:1.0"),
+            ("-x", "\"-x\""),
+            ("?x", "\"?x\""),
+            ("a #b", "\"a #b\""),
+            ("a:", "\"a:\""),
+            ("Yes", "\"Yes\""),
+            ("1.90.0", "\"1.90.0\""),
+            ("line\nbreak", "\"line\\nbreak\""),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(yaml_scalar(&json!(text)), expected, "{:?}", text);
+        }
+        assert_eq!(yaml_scalar(&json!(8080)), "8080");
+        assert_eq!(yaml_scalar(&json!(false)), "false");
+    }
+
+    #[test]
+    fn renders_nested_lists_of_objects() {
+        let value = json!({
+            "spec": {
+                "containers": [
+                    { "name": "api", "ports": [{ "containerPort": 80, "name": "http" }] },
+                    { "name": "sidecar", "args": ["--verbose", "true"] },
+                ],
+                "volumes": [],
+            },
+        });
+        let mut out = String::new();
+        to_yaml(&value, 0, &mut out);
+        assert_eq!(
+            out,
+            "spec:
+  containers:
+    - name: api
+      ports:
+        - containerPort: 80
+          name: http
+    - args:
+        - \"--verbose\"
+        - \"true\"
+      name: sidecar
+  volumes: []
+"
+        );
+    }
+
+    #[test]
+    fn parses_python_healthcheck() {
+        let stage = "FROM python:3.13-slim AS prod
+
+HEALTHCHECK --interval=30s --timeout=3s --start-period=40s --retries=3 \\
+    CMD python -c \"import urllib.request; urllib.request.urlopen('http://localhost:${APP_PORT}/health').read()\" || exit 1
+
+CMD [\"gunicorn\"]";
+        let check = parse_healthcheck(stage).unwrap();
+        assert_eq!(check.path.as_deref(), Some("/health"));
+        assert_eq!(check.interval, Some(30));
+        assert_eq!(check.timeout, Some(3));
+        assert_eq!(check.start_period, Some(40));
+        assert_eq!(check.retries, Some(3));
+    }
+
+    #[test]
+    fn parses_node_healthcheck() {
+        let stage = "HEALTHCHECK --interval=1m --timeout=3s --start-period=20s --retries=5 \\
+    CMD node -e \"require('http').get('http://localhost:' + process.env.APP_PORT + '/healthz', (r) => {process.exit(r.statusCode === 200 ? 0 : 1)}).on('error', () => process.exit(1))\"";
+        let check = parse_healthcheck(stage).unwrap();
+        assert_eq!(check.path.as_deref(), Some("/healthz"));
+        assert_eq!(check.interval, Some(60));
+        assert_eq!(check.retries, Some(5));
+    }
+
+    #[test]
+    fn parses_root_healthcheck_and_missing_one() {
+        let stage = "HEALTHCHECK --interval=30s \\
+    CMD wget --quiet --tries=1 --spider http://localhost:${APP_PORT}/ || exit 1";
+        assert_eq!(parse_healthcheck(stage).unwrap().path.as_deref(), Some("/"));
+        assert!(parse_healthcheck("FROM scratch\nCMD [\"app\"]").is_none());
+    }
+
+    #[test]
+    fn converts_durations() {
+        assert_eq!(seconds("30s"), Some(30));
+        assert_eq!(seconds("2m"), Some(120));
+        assert_eq!(seconds("soon"), None);
+    }
+
+    struct Quiet;
+
+    impl Reporter for Quiet {
+        fn report(&mut self, _event: crate::report::ReportEvent) {}
+    }
+
+    fn manifests(name: &str, stacks: &[&str], variables: &Variables) -> PathBuf {
+        let target =
+            std::env::temp_dir().join(format!("devkit-k8s-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&target);
+        let stacks: Vec<String> = stacks.iter().map(|s| s.to_string()).collect();
+        generate_k8s(
+            &stacks,
+            target.to_str().unwrap(),
+            variables,
+            false,
+            &mut Quiet,
+        )
+        .unwrap();
+        target.join("k8s")
+    }
+
+    #[test]
+    fn runs_local_images_unless_a_registry_is_set() {
+        let k8s = manifests("local", &["express"], &Variables::new());
+        let project = k8s
+            .parent()
+            .unwrap()
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .into_owned();
+        let yaml = std::fs::read_to_string(k8s.join("express.yaml")).unwrap();
+        assert!(yaml.contains(&format!("image: {}-express:latest\n", project)));
+        assert!(yaml.contains("imagePullPolicy: Never\n"));
+
+        let variables = Variables::from([
+            ("image_registry".to_string(), "ghcr.io/acme".to_string()),
+            ("image_tag".to_string(), "1.0".to_string()),
+        ]);
+        let k8s = manifests("registry", &["express"], &variables);
+        let yaml = std::fs::read_to_string(k8s.join("express.yaml")).unwrap();
+        assert!(yaml.contains("image: ghcr.io/acme/express:1.0\n"));
+        assert!(!yaml.contains("imagePullPolicy"));
+    }
+
+    #[test]
+    fn mounts_an_nginx_conf_without_the_docker_resolver() {
+        let variables = Variables::from([(
+            "api_upstream".to_string(),
+            "http://express:3000".to_string(),
+        )]);
+        let k8s = manifests("nginx", &["react"], &variables);
+        let yaml = std::fs::read_to_string(k8s.join("react.yaml")).unwrap();
+        assert!(
+            yaml.starts_with("# Generated by devkit: ConfigMap, Deployment, Service of React\n")
+        );
+        assert!(yaml.contains("  default.conf: |"));
+        assert!(yaml.contains("\n    # React static site served by nginx"));
+        assert!(yaml.contains("\n            proxy_pass http://express:3000;\n"));
+        assert!(!yaml.contains("127.0.0.11"));
+        assert!(yaml.contains("mountPath: /etc/nginx/conf.d/default.conf\n"));
+        assert!(yaml.contains("name: react-nginx\n"));
+    }
+}
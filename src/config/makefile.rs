@@ -0,0 +1,248 @@
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+
+use super::{
+    compat::make_recipes, component::get_component, embedded, load_stack, scaffold::project_name,
+    write_file, StackConfig, Variables,
+};
+use crate::report::Reporter;
+
+/// Generate the root Makefile including `common.mk`, every stack fragment and
+/// the make targets of the selected components. `variables` are the `--var`
+/// overrides, recorded for `devkit sync-mk`.
+pub fn generate_makefile(
+    stacks: &[String],
+    components: &[String],
+    target_path: &str,
+    variables: &Variables,
+    hardened: bool,
+    reporter: &mut dyn Reporter,
+) -> Result<()> {
+    let base_path = PathBuf::from(target_path);
+    let configs = stacks
+        .iter()
+        .map(|s| load_stack(s))
+        .collect::<Result<Vec<_>>>()?;
+
+    write_file(
+        &base_path.join("common.mk"),
+        &embedded::load("makefiles/common.mk")?,
+        reporter,
+    )
+    .context("Error creating common.mk")?;
+    write_file(
+        &base_path.join("Makefile"),
+        &format!(
+            "{}\n{}",
+            selection_header(stacks, components, variables, hardened),
+            render_makefile(&configs, components, &project_name(&base_path))
+        ),
+        reporter,
+    )
+    .context("Error creating Makefile")?;
+
+    Ok(())
+}
+
+/// Version and selection replayed by `devkit sync-mk`. Overrides are kept in
+/// `# DEVKIT_VAR: key=value` comments so their values never reach make.
+fn selection_header(
+    stacks: &[String],
+    components: &[String],
+    variables: &Variables,
+    hardened: bool,
+) -> String {
+    let overrides: String = variables
+        .iter()
+        .map(|(key, value)| format!("# DEVKIT_VAR: {}={}\n", key, value.replace('\n', " ")))
+        .collect();
+    format!(
+        "# Generated by devkit {version}; refresh with `devkit sync-mk`
+DEVKIT_VERSION := {version}
+DEVKIT_STACKS := {stacks}
+DEVKIT_COMPONENTS := {components}
+DEVKIT_HARDENED := {hardened}
+{overrides}",
+        version = env!("CARGO_PKG_VERSION"),
+        stacks = stacks.join(" "),
+        components = components.join(" "),
+        hardened = hardened,
+        overrides = overrides,
+    )
+}
+
+fn render_makefile(stacks: &[StackConfig], components: &[String], project_name: &str) -> String {
+    let includes = stacks
+        .iter()
+        .map(|s| s.makefile_path())
+        .chain(
+            components
+                .iter()
+                .filter_map(|c| get_component(c).ok()?.makefile()),
+        )
+        .collect::<Vec<_>>()
+        .join(" ");
+    let aggregates = aggregate_targets(stacks);
+
+    format!(
+        "# Project-specific variables
+PROJECT_NAME := {project_name}
+STACK := {stacks}
+
+# Package managers
+JS_PKG_MANAGER ?= pnpm
+PY_PKG_MANAGER ?= uv
+
+# Load .env (see .env.example) and export it to recipes and docker compose
+ifneq (,$(wildcard .env))
+include .env
+export
+endif
+
+# Files to include
+INCLUDES := common.mk {includes}
+
+# Check that files exist before including them
+$(foreach file,$(INCLUDES),$(if $(wildcard $(file)),,$(error File $(file) does not exist)))
+
+# Include files
+include $(INCLUDES)
+
+##@ All stacks
+
+.PHONY: {phony}
+{targets}",
+        project_name = project_name,
+        stacks = stacks
+            .iter()
+            .map(|s| s.id.as_str())
+            .collect::<Vec<_>>()
+            .join(" "),
+        includes = includes,
+        phony = aggregates
+            .iter()
+            .map(|(action, _)| action.clone())
+            .chain(stacks.iter().map(|s| format!("help-{}", s.id)))
+            .collect::<Vec<_>>()
+            .join(" "),
+        targets = render_aggregates(&aggregates, stacks),
+    )
+}
+
+/// `<action>-<stack>` recipes grouped by action, in the order they first appear
+fn aggregate_targets(stacks: &[StackConfig]) -> Vec<(String, Vec<String>)> {
+    let mut aggregates: Vec<(String, Vec<String>)> = Vec::new();
+    for stack in stacks {
+        let suffix = format!("-{}", stack.id);
+        for target in make_recipes(stack) {
+            let Some(action) = target.strip_suffix(&suffix) else {
+                continue;
+            };
+            match aggregates.iter_mut().find(|(a, _)| a == action) {
+                Some((_, targets)) => targets.push(target.clone()),
+                None => aggregates.push((action.to_string(), vec![target.clone()])),
+            }
+        }
+    }
+    aggregates
+}
+
+fn render_aggregates(aggregates: &[(String, Vec<String>)], stacks: &[StackConfig]) -> String {
+    let mut out = String::new();
+
+    for (action, targets) in aggregates {
+        out.push('\n');
+        if action == "dev" && targets.len() > 1 {
+            // Run every dev server at once, each line prefixed with its stack
+            out.push_str("dev: ## Start every development server in parallel\n");
+            out.push_str("\t@trap 'kill 0' INT TERM; \\\n");
+            for target in targets {
+                let label = target.trim_start_matches("dev-");
+                out.push_str(&format!(
+                    "\t$(MAKE) --no-print-directory {} 2>&1 | awk '{{ print \"[{}] \" $$0; fflush() }}' & \\\n",
+                    target, label
+                ));
+            }
+            out.push_str("\twait\n");
+        } else {
+            out.push_str(&format!(
+                "{}: {} ## {}\n",
+                action,
+                targets.join(" "),
+                describe(action, targets.len())
+            ));
+        }
+    }
+
+    for stack in stacks {
+        out.push_str(&format!(
+            "\nhelp-{}: ## Show {} commands\n\t$(call print-help,{})\n",
+            stack.id,
+            stack.name,
+            stack.makefile_path()
+        ));
+    }
+
+    out
+}
+
+/// Help text of an aggregate target
+fn describe(action: &str, count: usize) -> String {
+    let scope = if count > 1 {
+        "every stack"
+    } else {
+        "the stack"
+    };
+    match action {
+        "validate" => format!("Validate the environment of {}", scope),
+        "install" => format!("Install the dependencies of {}", scope),
+        "dev" => "Start the development server".to_string(),
+        "build" => format!("Build {}", scope),
+        "test" => format!("Run the tests of {}", scope),
+        "lint" => format!("Lint {}", scope),
+        "format" => format!("Format the code of {}", scope),
+        "clean" => format!("Clean the artifacts of {}", scope),
+        "upgrade" => format!("Update the dependencies of {}", scope),
+        "migrate" => "Apply database migrations".to_string(),
+        "makemigrations" => "Create database migrations".to_string(),
+        _ => format!("Run {} for {}", action, scope),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stacks(specs: &[&str]) -> Vec<StackConfig> {
+        specs.iter().map(|s| load_stack(s).unwrap()).collect()
+    }
+
+    #[test]
+    fn groups_stack_recipes_by_action() {
+        let aggregates = aggregate_targets(&stacks(&["vue", "fastapi@api"]));
+        let build = aggregates.iter().find(|(a, _)| a == "build").unwrap();
+        assert_eq!(build.1, ["build-vue", "build-fastapi"]);
+        assert_eq!(aggregates[0].0, "validate");
+        assert!(aggregates.iter().all(|(action, _)| !action.contains("vue")));
+    }
+
+    #[test]
+    fn runs_every_dev_server_in_parallel() {
+        let stacks = stacks(&["vue", "fastapi@api"]);
+        let out = render_aggregates(&aggregate_targets(&stacks), &stacks);
+        assert!(out.contains("\ndev: ## Start every development server in parallel\n"));
+        assert!(out.contains("\t$(MAKE) --no-print-directory dev-fastapi 2>&1 | awk '{ print \"[fastapi] \" $$0; fflush() }' & \\\n"));
+        assert!(out.contains("\nbuild: build-vue build-fastapi ## Build every stack\n"));
+        assert!(out.contains(
+            "\nhelp-fastapi: ## Show FastAPI commands\n\t$(call print-help,fastapi.mk)\n"
+        ));
+    }
+
+    #[test]
+    fn aliases_a_single_stack_directly() {
+        let stacks = stacks(&["go"]);
+        let out = render_aggregates(&aggregate_targets(&stacks), &stacks);
+        assert!(out.contains("\ndev: dev-go ## Start the development server\n"));
+        assert!(out.contains("\nbuild: build-go ## Build the stack\n"));
+    }
+}
@@ -1,13 +1,97 @@
+mod compat;
+mod component;
+mod compose;
+mod devcontainer;
+mod embedded;
+mod fragment;
+mod k8s;
+mod makefile;
+mod nginx;
+mod proxy;
+mod registry;
+mod scaffold;
+mod spec;
+mod stacks;
+mod status;
+mod sync;
+mod template;
+mod variant;
+
 use anyhow::{anyhow, Context, Result};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 
-#[derive(Debug, Serialize, Deserialize)]
+use crate::report::{NextStep, ReportEvent, Reporter};
+
+pub use compat::check_stacks;
+pub use component::{
+    apply_component, check_components, get_compatible_components, get_component,
+    AVAILABLE_COMPONENTS,
+};
+pub use compose::generate_compose;
+pub use devcontainer::generate_devcontainer;
+pub use embedded::{export_templates, set_template_dir};
+pub use fragment::MakeFragment;
+pub use k8s::generate_k8s;
+pub use makefile::generate_makefile;
+pub use nginx::link_api_upstream;
+pub use proxy::Proxy;
+pub use scaffold::is_empty_target;
+pub use spec::{split_stack_spec, StackSpec};
+pub use status::check_pins;
+pub use sync::{read_installed, sync_makefiles, Installed};
+pub use template::{parse_variable, TemplateVariable, Variables};
+pub use variant::{FilePatch, StackVariant, Stage};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct StackConfig {
+    /// Registry key, also used for the `<id>.mk` file and its targets
+    pub id: String,
     pub name: String,
     pub description: String,
+    /// Stacks this one builds on, merged in order before its own entries
+    pub extends: Vec<String>,
+    /// Stacks or bases that cannot share a directory with this one
+    pub conflicts: Vec<String>,
+    /// Stacks or bases that must also be selected somewhere in the project
+    pub requires: Vec<String>,
     pub files: Vec<FileTemplate>,
+    /// Make targets, merged by name along the `extends` chain
+    pub makefile: Option<MakeFragment>,
+    pub next_steps: Vec<NextStep>,
+    pub scaffold: Option<Scaffold>,
+    pub variables: Vec<TemplateVariable>,
+    pub variants: Vec<StackVariant>,
+    /// Variant applied by `with_variant`
+    pub variant: Option<String>,
+    /// Dockerfile patches of the hardened profile, applied by `with_hardening`
+    pub hardening: Vec<FilePatch>,
+    pub hardened: bool,
+    /// Serves an HTTP API that static stacks can proxy on `/api`
+    pub api: bool,
+    /// VS Code extensions recommended by the devcontainer
+    pub editor_extensions: Vec<String>,
+    /// Devcontainer image when the Dockerfile has no `dev` stage
+    pub dev_image: Option<String>,
+}
+
+/// Minimal starter app written when the target directory is empty
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Scaffold {
+    pub files: Vec<FileTemplate>,
+    pub next_steps: Vec<NextStep>,
+}
+
+/// Optional behaviour for `apply_stack_config`
+#[derive(Debug, Default, Clone)]
+pub struct ApplyOptions {
+    /// Scaffold a starter app when the target is empty or missing
+    pub scaffold: bool,
+    /// Overrides for template variables (`--var KEY=VALUE`)
+    pub variables: Variables,
+    /// Render the Dockerfiles with the hardened profile
+    pub hardened: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -16,89 +100,291 @@ pub struct FileTemplate {
     pub content: String,
 }
 
-pub const AVAILABLE_STACKS: [&str; 3] = ["vue", "nuxt", "fastapi"];
+pub const AVAILABLE_STACKS: [&str; 12] = [
+    "vue", "nuxt", "react", "next", "express", "nestjs", "fastapi", "django", "flask", "go",
+    "rust", "wasm",
+];
 
 pub fn get_available_stacks() -> Vec<String> {
     AVAILABLE_STACKS.iter().map(|s| s.to_string()).collect()
 }
 
-pub fn apply_stack_config(stack: &str, target_path: &str) -> Result<()> {
-    let config = get_stack_config(stack)?;
+pub fn apply_stack_config(
+    stack: &str,
+    target_path: &str,
+    options: &ApplyOptions,
+    reporter: &mut dyn Reporter,
+) -> Result<()> {
+    let mut config = load_stack(stack)?;
+    if options.hardened {
+        config = config.with_hardening()?;
+    }
+    let spec = StackSpec::parse(stack);
     let base_path = PathBuf::from(target_path);
+    let stack_path = spec.path(&base_path);
+    let scaffold = config
+        .scaffold
+        .as_ref()
+        .filter(|_| options.scaffold && is_empty_target(&stack_path));
+    let vars = stack_variables(&config, &spec, &base_path, &options.variables)?;
 
-    // Print current tree
-    println!("\n📂 Target directory: {}", base_path.display());
-    display_tree(&base_path, 0, 2)?;
+    // Report current tree
+    reporter.report(ReportEvent::TreeSnapshot {
+        title: format!("Target directory: {}", base_path.display()),
+        lines: collect_tree(&base_path, 2)?,
+    });
 
-    println!("\n🔧 Configuring stack {}...", config.name);
+    reporter.report(ReportEvent::StackStarted {
+        stack: config.display_name(),
+        target: stack_path.display().to_string(),
+    });
+
+    if let Some(scaffold) = scaffold {
+        scaffold::write_scaffold(scaffold, &stack_path, &vars, reporter)?;
+    }
 
     // Create configuration files (Dockerfile, Makefile, .dockerignore, etc.)
     for file in &config.files {
-        let file_path = base_path.join(&file.path);
-        create_file(&file_path, &file.content).context(format!("Error creating {}", file.path))?;
+        write_file(
+            &stack_file_path(&config, &base_path, &spec, &file.path),
+            &template::render(&file.content, &vars),
+            reporter,
+        )
+        .context(format!("Error creating {}", file.path))?;
+    }
+    if config.files.iter().any(|f| f.path == ".env.example") {
+        ignore_env_file(&base_path, reporter)?;
     }
 
-    println!("\n✓ Configuration complete!");
-    println!("\n📂 Updated tree:");
-    display_tree(&base_path, 0, 2)?;
+    reporter.report(ReportEvent::StackCompleted {
+        stack: config.display_name(),
+    });
+    reporter.report(ReportEvent::TreeSnapshot {
+        title: "Updated tree".to_string(),
+        lines: collect_tree(&base_path, 2)?,
+    });
 
-    println!("\n📝 Next steps:");
-    println!(
-        "  cd {}        # Change to project directory",
-        base_path.display()
-    );
-    println!("  make help      # See all available commands");
-    println!("  make install   # Install dependencies");
-    println!("  make dev       # Run in development");
+    let mut steps = vec![
+        NextStep::new(
+            format!("cd {}", base_path.display()),
+            "Change to project directory",
+        ),
+        NextStep::new("make help", "See all available commands"),
+    ];
+    if let Some(scaffold) = scaffold {
+        steps.extend(scaffold.next_steps.iter().cloned());
+    }
+    steps.extend(config.next_steps);
+    reporter.report(ReportEvent::NextSteps { steps });
 
     Ok(())
 }
 
-/// Display a directory tree
-fn display_tree(path: &Path, depth: usize, max_depth: usize) -> Result<()> {
-    if depth > max_depth {
+/// Overrides seen by `stack`: global ones, replaced by the `<stack>.<name>`
+/// ones; overrides scoped to other stacks are dropped
+fn scoped_overrides(stack: &str, overrides: &Variables) -> Variables {
+    let mut scoped: Variables = overrides
+        .iter()
+        .filter(|(name, _)| !name.contains('.'))
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect();
+    for (name, value) in overrides {
+        if let Some(name) = name.strip_prefix(stack).and_then(|n| n.strip_prefix('.')) {
+            scoped.insert(name.to_string(), value.clone());
+        }
+    }
+    scoped
+}
+
+/// Stack variables resolved against the builtins of a stack placed in `spec.dir()`
+pub(crate) fn stack_variables(
+    config: &StackConfig,
+    spec: &StackSpec,
+    base_path: &Path,
+    overrides: &Variables,
+) -> Result<Variables> {
+    let builtins = Variables::from([
+        (
+            "project_name".to_string(),
+            scaffold::project_name(base_path),
+        ),
+        ("stack".to_string(), config.id.clone()),
+        ("stack_label".to_string(), config.name.clone()),
+        (
+            "stack_dir".to_string(),
+            format!("{}_DIR", config.id.to_uppercase()),
+        ),
+        ("stack_path".to_string(), spec.dir().to_string()),
+        (
+            "env_prefix".to_string(),
+            match spec.dir() {
+                "." => String::new(),
+                _ => format!("{}_", config.id.to_uppercase()),
+            },
+        ),
+    ]);
+    let overrides = scoped_overrides(&config.id, overrides);
+    let mut vars = template::resolve_variables(&config.variables, &overrides, builtins)?;
+    if let Some(upstream) = vars.get("api_upstream") {
+        let resolver = vars.get("nginx_resolver").map_or("", String::as_str);
+        let location = nginx::api_location(upstream, resolver);
+        vars.insert("api_location".to_string(), location);
+    }
+    if let Some(sources) = vars.get("app_sources") {
+        let copies = sources
+            .split_whitespace()
+            .map(|path| format!("COPY --from=build --chown=app:app /app/{0} /app/{0}", path))
+            .collect::<Vec<_>>()
+            .join("\n");
+        vars.insert("app_copy".to_string(), copies);
+    }
+    Ok(vars)
+}
+
+/// Where a stack file lands; the `.mk` stays next to the root Makefile including it
+pub(crate) fn stack_file_path(
+    config: &StackConfig,
+    base_path: &Path,
+    spec: &StackSpec,
+    path: &str,
+) -> PathBuf {
+    if path == config.makefile_path() {
+        base_path.join(path)
+    } else {
+        spec.path(base_path).join(path)
+    }
+}
+
+/// Keep the `.env` copied from `.env.example` out of git
+fn ignore_env_file(base_path: &Path, reporter: &mut dyn Reporter) -> Result<()> {
+    append_missing_lines(
+        &base_path.join(".gitignore"),
+        "# Environment files (secrets)\n.env",
+        reporter,
+    )
+    .context("Error updating .gitignore")
+}
+
+/// Append the entries of `content` missing from the file at `path`, block by
+/// block, keeping each block's leading comments; existing lines are left as is
+pub(super) fn append_missing_lines(
+    path: &Path,
+    content: &str,
+    reporter: &mut dyn Reporter,
+) -> Result<()> {
+    let mut existing = fs::read_to_string(path).unwrap_or_default();
+    let present: Vec<String> = existing.lines().map(|l| l.trim().to_string()).collect();
+
+    let mut missing = String::new();
+    for block in content.split("\n\n") {
+        let (comments, entries): (Vec<&str>, Vec<&str>) = block
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .partition(|l| l.starts_with('#'));
+        let entries: Vec<&str> = entries
+            .into_iter()
+            .filter(|e| !present.iter().any(|p| p == e.trim()))
+            .collect();
+        if entries.is_empty() {
+            continue;
+        }
+        if !missing.is_empty() {
+            missing.push('\n');
+        }
+        for line in comments.iter().chain(&entries) {
+            missing.push_str(line);
+            missing.push('\n');
+        }
+    }
+    if missing.is_empty() {
+        reporter.report(ReportEvent::FileSkipped {
+            path: path.display().to_string(),
+            reason: "unchanged".to_string(),
+        });
         return Ok(());
     }
 
+    if !existing.is_empty() {
+        if !existing.ends_with('\n') {
+            existing.push('\n');
+        }
+        existing.push('\n');
+    }
+    existing.push_str(&missing);
+    write_file(path, &existing, reporter)
+}
+
+/// Collect a directory tree as display lines
+fn collect_tree(path: &Path, max_depth: usize) -> Result<Vec<String>> {
+    let mut lines = Vec::new();
+
     if !path.exists() {
-        println!(
-            "{}└── (empty or non-existent directory)",
-            "  ".repeat(depth)
-        );
+        lines.push("└── (empty or non-existent directory)".to_string());
+        return Ok(lines);
+    }
+
+    collect_tree_recursive(path, 0, max_depth, &mut lines)?;
+    Ok(lines)
+}
+
+fn collect_tree_recursive(
+    path: &Path,
+    depth: usize,
+    max_depth: usize,
+    lines: &mut Vec<String>,
+) -> Result<()> {
+    if depth > max_depth {
         return Ok(());
     }
 
-    let entries = fs::read_dir(path)
-        .context("Impossible de lire le répertoire")?
+    let mut entries = fs::read_dir(path)
+        .context("Unable to read directory")?
         .filter_map(|e| e.ok())
+        // Ignore hidden folders and node_modules
+        .filter(|e| {
+            let name = e.file_name();
+            let name = name.to_string_lossy();
+            !name.starts_with('.') && name != "node_modules" && name != "target"
+        })
         .collect::<Vec<_>>();
+    entries.sort_by_key(|e| e.file_name());
 
     for (i, entry) in entries.iter().enumerate() {
         let is_last = i == entries.len() - 1;
         let prefix = if is_last { "└── " } else { "├── " };
         let file_name = entry.file_name();
-        let file_name_str = file_name.to_string_lossy();
-
-        // Ignore hidden folders and node_modules
-        if file_name_str.starts_with('.')
-            || file_name_str == "node_modules"
-            || file_name_str == "target"
-        {
-            continue;
-        }
 
-        println!("{}{}{}", "  ".repeat(depth), prefix, file_name_str);
+        lines.push(format!(
+            "{}{}{}",
+            "  ".repeat(depth),
+            prefix,
+            file_name.to_string_lossy()
+        ));
 
         if entry.path().is_dir() && depth < max_depth {
-            display_tree(&entry.path(), depth + 1, max_depth)?;
+            collect_tree_recursive(&entry.path(), depth + 1, max_depth, lines)?;
         }
     }
 
     Ok(())
 }
 
-/// Create a file with its content
-fn create_file(path: &Path, content: &str) -> Result<()> {
+/// Create a file with its content, skipping it when already up to date
+pub(crate) fn write_file(path: &Path, content: &str, reporter: &mut dyn Reporter) -> Result<()> {
+    let display_path = path.display().to_string();
+
+    let existed = match fs::read_to_string(path) {
+        Ok(existing) if existing == content => {
+            reporter.report(ReportEvent::FileSkipped {
+                path: display_path,
+                reason: "unchanged".to_string(),
+            });
+            return Ok(());
+        }
+        Ok(_) => true,
+        Err(_) => path.exists(),
+    };
+
     // Create parent directories if necessary
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)
@@ -106,68 +392,68 @@ fn create_file(path: &Path, content: &str) -> Result<()> {
     }
 
     // Write the file
-    fs::write(path, content).context(format!("Unable to write {}", path.display()))?;
+    fs::write(path, content).context(format!("Unable to write {}", display_path))?;
 
-    println!("  ✓ {} created", path.display());
+    if existed {
+        reporter.report(ReportEvent::FileOverwritten { path: display_path });
+    } else {
+        reporter.report(ReportEvent::FileCreated { path: display_path });
+    }
     Ok(())
 }
 
-fn get_stack_config(stack_name: &str) -> Result<StackConfig> {
-    match stack_name {
-        "vue" => Ok(StackConfig {
-            name: "Vue".to_string(),
-            description: "Vue 3 application with TypeScript".to_string(),
-            files: vec![
-                FileTemplate {
-                    path: "vue.mk".to_string(),
-                    content: include_str!("../templates/vue/vue.mk").to_string(),
-                },
-                FileTemplate {
-                    path: "Dockerfile".to_string(),
-                    content: include_str!("../templates/vue/Dockerfile").to_string(),
-                },
-                FileTemplate {
-                    path: ".dockerignore".to_string(),
-                    content: include_str!("../templates/vue/.dockerignore").to_string(),
-                },
-            ],
-        }),
-        "nuxt" => Ok(StackConfig {
-            name: "Nuxt".to_string(),
-            description: "Nuxt 3 application with TypeScript".to_string(),
-            files: vec![
-                FileTemplate {
-                    path: "nuxt.mk".to_string(),
-                    content: include_str!("../templates/nuxt/nuxt.mk").to_string(),
-                },
-                FileTemplate {
-                    path: "Dockerfile".to_string(),
-                    content: include_str!("../templates/nuxt/Dockerfile").to_string(),
-                },
-                FileTemplate {
-                    path: ".dockerignore".to_string(),
-                    content: include_str!("../templates/nuxt/.dockerignore").to_string(),
-                },
-            ],
-        }),
-        "fastapi" => Ok(StackConfig {
-            name: "FastAPI".to_string(),
-            description: "REST API with FastAPI and Python".to_string(),
-            files: vec![
-                FileTemplate {
-                    path: "fastapi.mk".to_string(),
-                    content: include_str!("../templates/fastapi/fastapi.mk").to_string(),
-                },
-                FileTemplate {
-                    path: "Dockerfile".to_string(),
-                    content: include_str!("../templates/fastapi/Dockerfile").to_string(),
-                },
-                FileTemplate {
-                    path: ".dockerignore".to_string(),
-                    content: include_str!("../templates/fastapi/.dockerignore").to_string(),
-                },
-            ],
-        }),
-        _ => Err(anyhow!("Stack '{}' not recognized", stack_name)),
+/// Resolve a `stack[:variant]` spec into its configuration
+pub fn load_stack(spec: &str) -> Result<StackConfig> {
+    let (stack, variant) = split_stack_spec(spec);
+    // Bases are only reachable through `extends`
+    if !AVAILABLE_STACKS.contains(&stack) {
+        return Err(anyhow!("Stack '{}' not recognized", stack));
+    }
+    let config = registry::resolve_stack(stack)?;
+
+    match variant {
+        Some(variant) => config.with_variant(variant),
+        None => Ok(config),
+    }
+}
+
+/// Variant names of a stack, empty when it has a single flavour
+pub fn get_stack_variants(stack: &str) -> Result<Vec<String>> {
+    Ok(registry::resolve_stack(stack)?.variant_names())
+}
+
+impl StackConfig {
+    pub fn makefile_path(&self) -> String {
+        format!("{}.mk", self.id)
+    }
+
+    /// Paths written relative to the target for a stack placed in `dir`
+    pub fn produced_files(&self, dir: &str) -> Vec<String> {
+        let makefile_path = self.makefile_path();
+        self.files
+            .iter()
+            .map(|f| {
+                if f.path == makefile_path || dir == "." {
+                    f.path.clone()
+                } else {
+                    format!("{}/{}", dir, f.path)
+                }
+            })
+            .collect()
+    }
+
+    /// Name shown in reports, including the selected variant and profile
+    pub fn display_name(&self) -> String {
+        let flags: Vec<&str> = self
+            .variant
+            .as_deref()
+            .into_iter()
+            .chain(self.hardened.then_some("hardened"))
+            .collect();
+        if flags.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{} ({})", self.name, flags.join(", "))
+        }
     }
 }
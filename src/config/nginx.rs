@@ -0,0 +1,141 @@
+use anyhow::Result;
+use std::path::Path;
+
+use super::{load_stack, stack_variables, StackSpec, Variables};
+
+/// `location /api/` block of the generated `nginx.conf`, commented out
+/// until `api_upstream` is set. With a `resolver` the upstream is looked up per
+/// request, so nginx starts even when the backend is not up yet; without one
+/// it is resolved once at startup through the system resolver.
+pub(super) fn api_location(upstream: &str, resolver: &str) -> String {
+    let target = if upstream.is_empty() {
+        "http://api:8000"
+    } else {
+        upstream
+    };
+    let proxy = if resolver.is_empty() {
+        format!("proxy_pass {};", target)
+    } else {
+        format!(
+            "resolver {} valid=30s;\n    set $api_upstream {};\n    proxy_pass $api_upstream;",
+            resolver, target
+        )
+    };
+    let block = format!(
+        "location /api/ {{
+    {}
+    proxy_http_version 1.1;
+    proxy_set_header Host $host;
+    proxy_set_header X-Real-IP $remote_addr;
+    proxy_set_header X-Forwarded-For $proxy_add_x_forwarded_for;
+    proxy_set_header X-Forwarded-Proto $scheme;
+}}",
+        proxy
+    );
+
+    let (header, prefix) = if upstream.is_empty() {
+        (
+            "# Backend API, enabled with --var api_upstream=<url> or a co-selected backend stack",
+            "# ",
+        )
+    } else {
+        ("# Backend API, proxied with its /api prefix", "")
+    };
+    std::iter::once(header.to_string())
+        .chain(block.lines().map(|line| format!("{}{}", prefix, line)))
+        .map(|line| format!("    {}", line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Point `api_upstream` at the first selected backend stack, reachable by its
+/// compose service name on the port that stack is rendered with, unless it is
+/// set explicitly
+pub fn link_api_upstream(
+    stacks: &[String],
+    target_path: &str,
+    variables: &mut Variables,
+) -> Result<()> {
+    if variables.contains_key("api_upstream") {
+        return Ok(());
+    }
+    for stack in stacks {
+        let config = load_stack(stack)?;
+        if !config.api {
+            continue;
+        }
+        let spec = StackSpec::parse(stack);
+        let vars = stack_variables(&config, &spec, Path::new(target_path), variables)?;
+        if let Some(port) = vars.get("app_port") {
+            variables.insert(
+                "api_upstream".to_string(),
+                format!("http://{}:{}", config.id, port),
+            );
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn specs(stacks: &[&str]) -> Vec<String> {
+        stacks.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn comments_out_the_api_location_without_an_upstream() {
+        let block = api_location("", "127.0.0.11");
+        assert!(block.starts_with("    # Backend API, enabled with --var api_upstream="));
+        assert!(block.contains("\n    # location /api/ {\n"));
+        assert!(block.contains("\n    #     resolver 127.0.0.11 valid=30s;\n"));
+        assert!(block.lines().skip(1).all(|line| line.starts_with("    # ")));
+    }
+
+    #[test]
+    fn proxies_the_api_through_the_configured_resolver() {
+        let block = api_location("http://express:3000", "10.0.0.10");
+        assert!(block.contains("\n    location /api/ {\n"));
+        assert!(block.contains("\n        resolver 10.0.0.10 valid=30s;\n"));
+        assert!(block.contains("\n        set $api_upstream http://express:3000;\n"));
+        assert!(!block.contains("# location"));
+    }
+
+    #[test]
+    fn resolves_the_upstream_at_startup_without_a_resolver() {
+        let block = api_location("http://express:3000", "");
+        assert!(block.contains("\n        proxy_pass http://express:3000;\n"));
+        assert!(!block.contains("resolver"));
+        assert!(!block.contains("$api_upstream"));
+    }
+
+    #[test]
+    fn links_the_first_backend_on_its_rendered_port() {
+        let mut variables = Variables::new();
+        link_api_upstream(
+            &specs(&["vue", "fastapi@api", "express@svc"]),
+            "demo",
+            &mut variables,
+        )
+        .unwrap();
+        assert_eq!(variables["api_upstream"], "http://fastapi:8000");
+
+        let mut variables = Variables::from([("express.app_port".to_string(), "4000".to_string())]);
+        link_api_upstream(&specs(&["react", "express@api"]), "demo", &mut variables).unwrap();
+        assert_eq!(variables["api_upstream"], "http://express:4000");
+    }
+
+    #[test]
+    fn keeps_an_explicit_upstream_and_ignores_frontends() {
+        let upstream = "http://backend:9000".to_string();
+        let mut variables = Variables::from([("api_upstream".to_string(), upstream.clone())]);
+        link_api_upstream(&specs(&["vue", "express@api"]), "demo", &mut variables).unwrap();
+        assert_eq!(variables["api_upstream"], upstream);
+
+        let mut variables = Variables::new();
+        link_api_upstream(&specs(&["vue"]), "demo", &mut variables).unwrap();
+        assert!(!variables.contains_key("api_upstream"));
+    }
+}
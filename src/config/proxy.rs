@@ -0,0 +1,111 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use super::{embedded, scaffold, template, write_file, Variables};
+use crate::report::{ReportEvent, Reporter};
+
+/// Entrypoint routing `/` to the frontend and `/api` to the backend when
+/// `compose.yaml` runs both
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Proxy {
+    Caddy,
+    Traefik,
+}
+
+/// Compose service reachable by the proxy, on its `app_port`
+pub(super) struct Upstream {
+    pub id: String,
+    pub port: String,
+    /// Port published on the host
+    pub host_port: String,
+    pub api: bool,
+}
+
+impl Proxy {
+    /// Config file written next to `compose.yaml`
+    fn config_file(self) -> &'static str {
+        match self {
+            Proxy::Caddy => "Caddyfile",
+            Proxy::Traefik => "traefik.yml",
+        }
+    }
+
+    fn template(self) -> Result<String> {
+        embedded::load(&format!("proxy/{}", self.config_file()))
+    }
+
+    /// Image, command and mounted config of the `proxy` compose service
+    fn service(self) -> String {
+        match self {
+            Proxy::Caddy => "    image: docker.io/caddy:2-alpine\n    \
+                 volumes:\n      - ./Caddyfile:/etc/caddy/Caddyfile:ro\n"
+                .to_string(),
+            Proxy::Traefik => "    image: docker.io/traefik:v3.5\n    \
+                 command:\n      - --entrypoints.web.address=:80\n      \
+                 - --providers.file.filename=/etc/traefik/dynamic.yml\n    \
+                 volumes:\n      - ./traefik.yml:/etc/traefik/dynamic.yml:ro\n"
+                .to_string(),
+        }
+    }
+}
+
+/// Write the proxy config routing the first frontend and backend of
+/// `upstreams`, and return its `compose.yaml` service; `None` unless both
+/// kinds are selected
+pub(super) fn generate_proxy(
+    proxy: Proxy,
+    upstreams: &[Upstream],
+    base_path: &Path,
+    reporter: &mut dyn Reporter,
+) -> Result<Option<String>> {
+    let Some(frontend) = upstreams.iter().find(|u| !u.api) else {
+        return Ok(None);
+    };
+    let Some(api) = upstreams.iter().find(|u| u.api) else {
+        return Ok(None);
+    };
+    for skipped in upstreams
+        .iter()
+        .filter(|u| u.id != frontend.id && u.id != api.id)
+    {
+        reporter.report(ReportEvent::Warning {
+            message: format!(
+                "{} is not routed by the proxy (only {} and {} are)",
+                skipped.id, frontend.id, api.id
+            ),
+        });
+    }
+
+    let vars = Variables::from([
+        (
+            "project_name".to_string(),
+            scaffold::project_name(base_path),
+        ),
+        ("frontend".to_string(), frontend.id.clone()),
+        ("frontend_port".to_string(), frontend.port.clone()),
+        ("api".to_string(), api.id.clone()),
+        ("api_port".to_string(), api.port.clone()),
+    ]);
+    let path = base_path.join(proxy.config_file());
+    write_file(
+        &path,
+        &template::render(&proxy.template()?, &vars),
+        reporter,
+    )
+    .with_context(|| format!("Error creating {}", proxy.config_file()))?;
+
+    // Publish on the first free port from 8080 so no stack port is shadowed
+    let mut port = 8080;
+    while upstreams.iter().any(|u| u.host_port == port.to_string()) {
+        port += 1;
+    }
+
+    Ok(Some(format!(
+        "  proxy:\n{}    ports:\n      - \"${{PROXY_PORT:-{}}}:80\"\n    \
+         depends_on:\n      - {}\n      - {}\n",
+        proxy.service(),
+        port,
+        frontend.id,
+        api.id
+    )))
+}
@@ -0,0 +1,216 @@
+use anyhow::{anyhow, Context, Result};
+
+use super::{stacks, FileTemplate, StackConfig};
+
+/// Definition of a stack with its `extends` chain merged in, `.mk` rendered as a
+/// file under its own `help` section
+pub fn resolve_stack(stack: &str) -> Result<StackConfig> {
+    let mut config = resolve(stack, &mut Vec::new())?;
+
+    if let Some(makefile) = config.makefile.take() {
+        config.files.insert(
+            0,
+            FileTemplate {
+                path: format!("{}.mk", config.id),
+                content: format!("##@ {{{{stack_label}}}}\n\n{}", makefile.render()),
+            },
+        );
+    }
+    Ok(config)
+}
+
+fn resolve(stack: &str, chain: &mut Vec<String>) -> Result<StackConfig> {
+    if chain.iter().any(|s| s == stack) {
+        return Err(anyhow!(
+            "Stack inheritance cycle: {} -> {}",
+            chain.join(" -> "),
+            stack
+        ));
+    }
+    let definition =
+        stacks::definition(stack)?.ok_or_else(|| anyhow!("Stack '{}' not recognized", stack))?;
+
+    chain.push(stack.to_string());
+    let mut resolved: Option<StackConfig> = None;
+    for parent in &definition.extends {
+        let parent = resolve(parent, chain)
+            .with_context(|| format!("Unable to resolve base of {}", stack))?;
+        resolved = Some(match resolved {
+            Some(base) => overlay(base, parent),
+            None => parent,
+        });
+    }
+    chain.pop();
+
+    Ok(match resolved {
+        Some(base) => overlay(base, definition),
+        None => definition,
+    })
+}
+
+/// Merge `child` on top of `base`: entries with the same key are replaced,
+/// new ones appended
+fn overlay(mut base: StackConfig, child: StackConfig) -> StackConfig {
+    for file in child.files {
+        match base.files.iter_mut().find(|f| f.path == file.path) {
+            Some(existing) => *existing = file,
+            None => base.files.push(file),
+        }
+    }
+
+    for name in child.conflicts {
+        if !base.conflicts.contains(&name) {
+            base.conflicts.push(name);
+        }
+    }
+    base.hardening.extend(child.hardening);
+    for extension in child.editor_extensions {
+        if !base.editor_extensions.contains(&extension) {
+            base.editor_extensions.push(extension);
+        }
+    }
+    for name in child.requires {
+        if !base.requires.contains(&name) {
+            base.requires.push(name);
+        }
+    }
+
+    for var in child.variables {
+        match base.variables.iter_mut().find(|v| v.name == var.name) {
+            // An empty description only overrides the default value
+            Some(existing) if var.description.is_empty() => existing.default = var.default,
+            Some(existing) => *existing = var,
+            None => base.variables.push(var),
+        }
+    }
+
+    for variant in child.variants {
+        match base.variants.iter_mut().find(|v| v.name == variant.name) {
+            Some(existing) => *existing = variant,
+            None => base.variants.push(variant),
+        }
+    }
+
+    base.makefile = match (base.makefile, child.makefile) {
+        (Some(mut parent), Some(child)) => {
+            parent.merge(&child);
+            Some(parent)
+        }
+        (parent, child) => child.or(parent),
+    };
+
+    if !child.next_steps.is_empty() {
+        base.next_steps = child.next_steps;
+    }
+
+    StackConfig {
+        id: child.id,
+        name: child.name,
+        description: child.description,
+        extends: child.extends,
+        scaffold: child.scaffold.or(base.scaffold),
+        api: child.api || base.api,
+        dev_image: child.dev_image.or(base.dev_image),
+        ..base
+    }
+}
+
+/// The stack followed by every stack it extends, nearest first
+pub fn lineage(stack: &str) -> Result<Vec<String>> {
+    let mut lineage = vec![stack.to_string()];
+    let mut i = 0;
+    while i < lineage.len() {
+        if let Some(definition) = stacks::definition(&lineage[i])? {
+            for parent in definition.extends {
+                if !lineage.contains(&parent) {
+                    lineage.push(parent);
+                }
+            }
+        }
+        i += 1;
+    }
+    Ok(lineage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{MakeFragment, TemplateVariable};
+
+    fn file(path: &str, content: &str) -> FileTemplate {
+        FileTemplate {
+            path: path.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn overlays_child_entries_on_the_base() {
+        let base = StackConfig {
+            id: "base".to_string(),
+            conflicts: vec!["base".to_string()],
+            files: vec![file("Dockerfile", "base"), file(".dockerignore", "base")],
+            variables: vec![TemplateVariable::new("app_port", "3000", "Port")],
+            makefile: Some(MakeFragment::parse(
+                "DIR ?= .\n\nbuild: ## Build\n\tbase build\n",
+            )),
+            api: true,
+            ..Default::default()
+        };
+        let child = StackConfig {
+            id: "child".to_string(),
+            conflicts: vec!["base".to_string(), "other".to_string()],
+            files: vec![file("Dockerfile", "child"), file("index.html", "child")],
+            variables: vec![
+                TemplateVariable::override_default("app_port", "5173"),
+                TemplateVariable::new("flavor", "plain", "Flavor"),
+            ],
+            makefile: Some(MakeFragment::parse("test: ## Test\n\tchild test\n")),
+            ..Default::default()
+        };
+
+        let merged = overlay(base, child);
+        assert_eq!(merged.id, "child");
+        assert!(merged.api);
+        assert_eq!(merged.conflicts, ["base", "other"]);
+        let files: Vec<_> = merged
+            .files
+            .iter()
+            .map(|f| (f.path.as_str(), f.content.as_str()))
+            .collect();
+        assert_eq!(
+            files,
+            [
+                ("Dockerfile", "child"),
+                (".dockerignore", "base"),
+                ("index.html", "child")
+            ]
+        );
+        let port = &merged.variables[0];
+        assert_eq!(
+            (port.default.as_str(), port.description.as_str()),
+            ("5173", "Port")
+        );
+        assert_eq!(merged.variables[1].name, "flavor");
+        let makefile = merged.makefile.unwrap().render();
+        assert!(makefile.contains("base build"));
+        assert!(makefile.contains("child test"));
+    }
+
+    #[test]
+    fn resolves_extends_chains_nearest_first() {
+        assert_eq!(lineage("vue").unwrap(), ["vue", "vite-base", "node-base"]);
+        let vue = resolve_stack("vue").unwrap();
+        assert_eq!(vue.files[0].path, "vue.mk");
+        assert!(vue.files[0].content.starts_with("##@ {{stack_label}}\n\n"));
+        assert!(vue.files.iter().any(|f| f.path == ".dockerignore"));
+        let port = vue.variables.iter().find(|v| v.name == "app_port").unwrap();
+        assert_eq!(port.default, "5173");
+    }
+
+    #[test]
+    fn rejects_unknown_stacks() {
+        let error = resolve_stack("cobol").unwrap_err().to_string();
+        assert_eq!(error, "Stack 'cobol' not recognized");
+    }
+}
@@ -0,0 +1,51 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+use super::{
+    template::{render, Variables},
+    write_file, Scaffold,
+};
+use crate::report::Reporter;
+
+/// True when the target does not exist or only holds hidden entries (e.g. `.git`)
+pub fn is_empty_target(path: &Path) -> bool {
+    let Ok(entries) = fs::read_dir(path) else {
+        return !path.exists();
+    };
+
+    entries
+        .filter_map(|e| e.ok())
+        .all(|e| e.file_name().to_string_lossy().starts_with('.'))
+}
+
+/// Write the starter app files, rendering template variables
+pub(super) fn write_scaffold(
+    scaffold: &Scaffold,
+    base_path: &Path,
+    vars: &Variables,
+    reporter: &mut dyn Reporter,
+) -> Result<()> {
+    for file in &scaffold.files {
+        let content = render(&file.content, vars);
+        write_file(&base_path.join(&file.path), &content, reporter)
+            .context(format!("Error scaffolding {}", file.path))?;
+    }
+
+    Ok(())
+}
+
+/// Directory name, usable as a package name
+pub(super) fn project_name(base_path: &Path) -> String {
+    let name = base_path
+        .canonicalize()
+        .ok()
+        .or_else(|| std::env::current_dir().ok().map(|cwd| cwd.join(base_path)))
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "my-project".to_string());
+
+    name.to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
@@ -0,0 +1,50 @@
+use std::path::{Path, PathBuf};
+
+/// Stack selection as given on the command line: `stack[:variant][@dir]`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StackSpec<'a> {
+    pub stack: &'a str,
+    pub variant: Option<&'a str>,
+    /// Subdirectory of the target holding the stack files
+    pub dir: Option<&'a str>,
+}
+
+impl<'a> StackSpec<'a> {
+    pub fn parse(spec: &'a str) -> Self {
+        let (rest, dir) = match spec.rsplit_once('@') {
+            Some((rest, dir)) => {
+                let dir = dir.trim_start_matches("./").trim_matches('/');
+                (rest, Some(dir).filter(|d| !d.is_empty() && *d != "."))
+            }
+            None => (spec, None),
+        };
+        let (stack, variant) = match rest.split_once(':') {
+            Some((stack, variant)) => (stack, Some(variant)),
+            None => (rest, None),
+        };
+        Self {
+            stack,
+            variant,
+            dir,
+        }
+    }
+
+    /// Stack directory relative to the target, `.` when not set
+    pub fn dir(&self) -> &'a str {
+        self.dir.unwrap_or(".")
+    }
+
+    /// Directory of the stack under `base`, without a `./` component for the root
+    pub fn path(&self, base: &Path) -> PathBuf {
+        match self.dir {
+            Some(dir) => base.join(dir),
+            None => base.to_path_buf(),
+        }
+    }
+}
+
+/// Split `stack[:variant]` into its parts, ignoring any `@dir`
+pub fn split_stack_spec(spec: &str) -> (&str, Option<&str>) {
+    let spec = StackSpec::parse(spec);
+    (spec.stack, spec.variant)
+}
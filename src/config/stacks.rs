@@ -0,0 +1,759 @@
+use anyhow::Result;
+
+use super::{
+    embedded, FilePatch, FileTemplate, MakeFragment, Scaffold, StackConfig, StackVariant, Stage,
+    TemplateVariable,
+};
+use crate::report::NextStep;
+
+/// Hardened profile swapping the prod stage for an unprivileged one
+fn harden_prod_stage(hardened: String) -> FilePatch {
+    FilePatch::ReplaceStage {
+        path: "Dockerfile".to_string(),
+        stage: Stage::Prod,
+        content: hardened,
+    }
+}
+
+/// Hardened profile of a prod stage already running as non-root: only the
+/// runtime image digest is added
+fn pin_runtime_image(from_line: &str) -> FilePatch {
+    FilePatch::Substitute {
+        path: "Dockerfile".to_string(),
+        from: from_line.to_string(),
+        to: from_line.replace(" AS prod", "{{runtime_digest}} AS prod"),
+    }
+}
+
+/// SPA `nginx.conf` copied into the nginx prod stage
+fn nginx_conf() -> Result<FileTemplate> {
+    Ok(FileTemplate {
+        path: "nginx.conf".to_string(),
+        content: embedded::load("nginx/nginx.conf")?,
+    })
+}
+
+/// Backend proxied by the generated `nginx.conf`, linked by `link_api_upstream`
+fn api_upstream() -> TemplateVariable {
+    TemplateVariable::new(
+        "api_upstream",
+        "",
+        "Backend proxied on /api by nginx (e.g. http://fastapi:8000)",
+    )
+}
+
+/// DNS server nginx looks `api_upstream` up with at request time
+fn nginx_resolver() -> TemplateVariable {
+    TemplateVariable::new(
+        "nginx_resolver",
+        "127.0.0.11",
+        "DNS server resolving api_upstream per request (Docker's), empty to resolve it once at startup",
+    )
+}
+
+/// Endpoint polled by the Docker healthcheck and the Kubernetes probes
+fn health_path() -> TemplateVariable {
+    TemplateVariable::new(
+        "health_path",
+        "/health",
+        "Path answering 200 for the Docker healthcheck",
+    )
+}
+
+/// Hardened nginx runs unprivileged and cannot bind port 80
+fn nginx_listen_unprivileged() -> FilePatch {
+    FilePatch::Substitute {
+        path: "nginx.conf".to_string(),
+        from: "    listen 80;".to_string(),
+        to: "    listen 8080;".to_string(),
+    }
+}
+
+/// Raw stack definition, before its `extends` chain is resolved
+pub(super) fn definition(stack: &str) -> Result<Option<StackConfig>> {
+    let config = match stack {
+        // Bases, only reachable through `extends`
+        "node-base" => StackConfig {
+            id: "node-base".to_string(),
+            name: "Node".to_string(),
+            description: "JavaScript project driven by JS_PKG_MANAGER".to_string(),
+            // One package.json per directory
+            conflicts: vec!["node-base".to_string()],
+            files: vec![
+                FileTemplate {
+                    path: ".dockerignore".to_string(),
+                    content: embedded::load("node/.dockerignore")?,
+                },
+                FileTemplate {
+                    path: ".env.example".to_string(),
+                    content: embedded::load("node/.env.example")?,
+                },
+                FileTemplate {
+                    path: ".nvmrc".to_string(),
+                    content: embedded::load("node/.nvmrc")?,
+                },
+                FileTemplate {
+                    path: ".node-version".to_string(),
+                    content: embedded::load("node/.node-version")?,
+                },
+                FileTemplate {
+                    path: ".tool-versions".to_string(),
+                    content: embedded::load("node/.tool-versions")?,
+                },
+            ],
+            makefile: Some(MakeFragment::parse(&embedded::load("node/node.mk")?)),
+            variables: vec![
+                TemplateVariable::new(
+                    "clean_dirs",
+                    "dist",
+                    "Build output directories removed by make clean",
+                ),
+                TemplateVariable::new("app_port", "3000", "Port the application listens on"),
+                TemplateVariable::new("node_version", "20", "Node.js version of the images"),
+                TemplateVariable::new("pnpm_version", "10", "pnpm version of the images"),
+            ],
+            editor_extensions: vec![
+                "dbaeumer.vscode-eslint".to_string(),
+                "esbenp.prettier-vscode".to_string(),
+            ],
+            dev_image: Some("docker.io/node:{{node_version}}".to_string()),
+            ..Default::default()
+        },
+        "vite-base" => StackConfig {
+            id: "vite-base".to_string(),
+            name: "Vite".to_string(),
+            description: "Vite SPA served by nginx".to_string(),
+            extends: vec!["node-base".to_string()],
+            files: vec![
+                FileTemplate {
+                    path: "Dockerfile".to_string(),
+                    content: embedded::load("vite/Dockerfile")?,
+                },
+                FileTemplate {
+                    path: ".env.example".to_string(),
+                    content: embedded::load("vite/.env.example")?,
+                },
+                FileTemplate {
+                    path: ".tool-versions".to_string(),
+                    content: embedded::load("vite/.tool-versions")?,
+                },
+                nginx_conf()?,
+            ],
+            variables: vec![
+                TemplateVariable::override_default("app_port", "5173"),
+                api_upstream(),
+                nginx_resolver(),
+            ],
+            hardening: vec![
+                harden_prod_stage(embedded::load("vite/hardened.Dockerfile")?),
+                nginx_listen_unprivileged(),
+            ],
+            ..Default::default()
+        },
+        "node-api-base" => StackConfig {
+            id: "node-api-base".to_string(),
+            name: "Node API".to_string(),
+            description: "Compiled Node server running as a non-root user".to_string(),
+            extends: vec!["node-base".to_string()],
+            files: vec![FileTemplate {
+                path: "Dockerfile".to_string(),
+                content: embedded::load("node/Dockerfile")?,
+            }],
+            variables: vec![
+                TemplateVariable::new(
+                    "build_command",
+                    "pnpm run build",
+                    "Command compiling the app into dist/",
+                ),
+                TemplateVariable::new(
+                    "entrypoint",
+                    "dist/index.js",
+                    "Compiled entry file started by node",
+                ),
+                health_path(),
+            ],
+            hardening: vec![pin_runtime_image("FROM docker.io/node:${NODE_VERSION}-alpine AS prod")],
+            api: true,
+            ..Default::default()
+        },
+        "python-base" => StackConfig {
+            id: "python-base".to_string(),
+            name: "Python".to_string(),
+            description: "uv-managed Python project".to_string(),
+            // One pyproject.toml per directory
+            conflicts: vec!["python-base".to_string()],
+            files: vec![
+                FileTemplate {
+                    path: "Dockerfile".to_string(),
+                    content: embedded::load("python/Dockerfile")?,
+                },
+                FileTemplate {
+                    path: ".dockerignore".to_string(),
+                    content: embedded::load("python/.dockerignore")?,
+                },
+                FileTemplate {
+                    path: ".env.example".to_string(),
+                    content: embedded::load("python/.env.example")?,
+                },
+                FileTemplate {
+                    path: ".python-version".to_string(),
+                    content: embedded::load("python/.python-version")?,
+                },
+                FileTemplate {
+                    path: ".tool-versions".to_string(),
+                    content: embedded::load("python/.tool-versions")?,
+                },
+            ],
+            makefile: Some(MakeFragment::parse(&embedded::load("python/python.mk")?)),
+            variables: vec![
+                TemplateVariable::new(
+                    "dev_command",
+                    "uv run python main.py",
+                    "Development server command, also used by make dev",
+                ),
+                TemplateVariable::new(
+                    "build_step",
+                    "# Optional: Run any build steps if needed (e.g., compile translations, assets)\n\
+                     # RUN uv run python -m compileall .",
+                    "Dockerfile instructions run in the build stage",
+                ),
+                TemplateVariable::new(
+                    "prod_command",
+                    "python main.py",
+                    "Production server command, run from the virtualenv",
+                ),
+                TemplateVariable::new(
+                    "clean_dirs",
+                    "",
+                    "Extra directories removed by make clean",
+                ),
+                TemplateVariable::new(
+                    "app_sources",
+                    "main.py",
+                    "Files and packages copied into the hardened image next to .venv",
+                ),
+                TemplateVariable::new("app_port", "8000", "Port the application listens on"),
+                TemplateVariable::new("python_version", "3.13", "Python version of the images"),
+                TemplateVariable::new("uv_version", "0.9", "uv version of the images"),
+                TemplateVariable::new(
+                    "python_env",
+                    "development",
+                    "PYTHON_ENV written to .env.example",
+                ),
+                health_path(),
+            ],
+            hardening: vec![harden_prod_stage(embedded::load("python/hardened.Dockerfile")?)],
+            api: true,
+            editor_extensions: vec![
+                "ms-python.python".to_string(),
+                "charliermarsh.ruff".to_string(),
+            ],
+            ..Default::default()
+        },
+
+        "vue" => StackConfig {
+            id: "vue".to_string(),
+            name: "Vue".to_string(),
+            description: "Vue 3 application with TypeScript".to_string(),
+            extends: vec!["vite-base".to_string()],
+            next_steps: vec![
+                NextStep::new("make validate-vue", "Check the package manager"),
+                NextStep::new("make dev-vue", "Start the Vite dev server"),
+                NextStep::new("make build-vue", "Build static assets into dist/"),
+                NextStep::new(
+                    "docker build --target prod .",
+                    "Build the nginx production image",
+                ),
+            ],
+            scaffold: Some(Scaffold {
+                files: vec![
+                    FileTemplate {
+                        path: "package.json".to_string(),
+                        content: embedded::load("vue/scaffold/package.json")?,
+                    },
+                    FileTemplate {
+                        path: "vite.config.ts".to_string(),
+                        content: embedded::load("vue/scaffold/vite.config.ts")?,
+                    },
+                    FileTemplate {
+                        path: "index.html".to_string(),
+                        content: embedded::load("vue/scaffold/index.html")?,
+                    },
+                    FileTemplate {
+                        path: "src/main.ts".to_string(),
+                        content: embedded::load("vue/scaffold/src/main.ts")?,
+                    },
+                    FileTemplate {
+                        path: "src/App.vue".to_string(),
+                        content: embedded::load("vue/scaffold/src/App.vue")?,
+                    },
+                    FileTemplate {
+                        path: "src/env.d.ts".to_string(),
+                        content: embedded::load("vue/scaffold/src/env.d.ts")?,
+                    },
+                ],
+                next_steps: vec![NextStep::new(
+                    "pnpm install",
+                    "Create pnpm-lock.yaml for the Docker build",
+                )],
+            }),
+            variants: vec![
+                StackVariant::new("nginx", "Serve the build with nginx"),
+                StackVariant::new("caddy", "Serve the build with Caddy")
+                    .patch(FilePatch::ReplaceStage {
+                        path: "Dockerfile".to_string(),
+                        stage: Stage::NginxProd,
+                        content: embedded::load("vue/caddy.Dockerfile")?,
+                    })
+                    .patch(FilePatch::Remove {
+                        path: "nginx.conf".to_string(),
+                    })
+                    .hardening(harden_prod_stage(embedded::load("vue/caddy.hardened.Dockerfile")?))
+                    .next_steps(vec![
+                        NextStep::new("make dev-vue", "Start the Vite dev server"),
+                        NextStep::new(
+                            "docker build --target prod .",
+                            "Build the Caddy production image",
+                        ),
+                    ]),
+            ],
+            editor_extensions: vec!["Vue.volar".to_string()],
+            ..Default::default()
+        },
+        "nuxt" => StackConfig {
+            id: "nuxt".to_string(),
+            name: "Nuxt".to_string(),
+            description: "Nuxt 3 application with TypeScript".to_string(),
+            extends: vec!["node-base".to_string()],
+            files: vec![FileTemplate {
+                path: "Dockerfile".to_string(),
+                content: embedded::load("nuxt/Dockerfile")?,
+            }],
+            next_steps: vec![
+                NextStep::new("make validate-nuxt", "Check the package manager"),
+                NextStep::new("make dev-nuxt", "Start the Nuxt dev server"),
+                NextStep::new("make build-nuxt", "Build the .output server bundle"),
+                NextStep::new("docker build .", "Build the Node production image"),
+            ],
+            variables: vec![
+                TemplateVariable::override_default("clean_dirs", ".nuxt .output dist"),
+                api_upstream(),
+                nginx_resolver(),
+            ],
+            scaffold: Some(Scaffold {
+                files: vec![
+                    FileTemplate {
+                        path: "package.json".to_string(),
+                        content: embedded::load("nuxt/scaffold/package.json")?,
+                    },
+                    FileTemplate {
+                        path: "nuxt.config.ts".to_string(),
+                        content: embedded::load("nuxt/scaffold/nuxt.config.ts")?,
+                    },
+                    FileTemplate {
+                        path: "app.vue".to_string(),
+                        content: embedded::load("nuxt/scaffold/app.vue")?,
+                    },
+                ],
+                next_steps: vec![NextStep::new(
+                    "pnpm install",
+                    "Create pnpm-lock.yaml for the Docker build",
+                )],
+            }),
+            variants: vec![
+                StackVariant::new("ssr", "Node server from .output/server/index.mjs"),
+                StackVariant::new("static", "Prerendered site served by nginx")
+                    .patch(FilePatch::Replace {
+                        path: "Dockerfile".to_string(),
+                        content: embedded::load("nuxt/Dockerfile.static")?,
+                    })
+                    .patch(FilePatch::Replace {
+                        path: "nginx.conf".to_string(),
+                        content: embedded::load("nginx/nginx.conf")?,
+                    })
+                    .hardening(harden_prod_stage(embedded::load("nuxt/static.hardened.Dockerfile")?))
+                    .hardening(nginx_listen_unprivileged())
+                    .next_steps(vec![
+                        NextStep::new("make dev-nuxt", "Start the Nuxt dev server"),
+                        NextStep::new("pnpm run generate", "Prerender into .output/public"),
+                        NextStep::new("docker build .", "Build the nginx image"),
+                    ]),
+            ],
+            hardening: vec![harden_prod_stage(embedded::load("nuxt/hardened.Dockerfile")?)],
+            editor_extensions: vec!["Vue.volar".to_string()],
+            ..Default::default()
+        },
+        "react" => StackConfig {
+            id: "react".to_string(),
+            name: "React".to_string(),
+            description: "React SPA built with Vite".to_string(),
+            extends: vec!["vite-base".to_string()],
+            next_steps: vec![
+                NextStep::new("make validate-react", "Check the package manager"),
+                NextStep::new("make dev-react", "Start the Vite dev server"),
+                NextStep::new("make build-react", "Build static assets into dist/"),
+                NextStep::new(
+                    "docker build --target prod .",
+                    "Build the nginx production image",
+                ),
+            ],
+            ..Default::default()
+        },
+        "next" => StackConfig {
+            id: "next".to_string(),
+            name: "Next.js".to_string(),
+            description: "Next.js application with a standalone Node server".to_string(),
+            extends: vec!["node-base".to_string()],
+            files: vec![FileTemplate {
+                path: "Dockerfile".to_string(),
+                content: embedded::load("next/Dockerfile")?,
+            }],
+            makefile: Some(MakeFragment::parse(&embedded::load("next/next.mk")?)),
+            next_steps: vec![
+                NextStep::new(
+                    "output: 'standalone'",
+                    "Set it in next.config for the Docker image",
+                ),
+                NextStep::new("make dev-next", "Start the Next.js dev server"),
+                NextStep::new("make build-next", "Build the .next standalone bundle"),
+                NextStep::new("docker build .", "Build the Node production image"),
+            ],
+            variables: vec![TemplateVariable::override_default("clean_dirs", ".next out")],
+            hardening: vec![harden_prod_stage(embedded::load("next/hardened.Dockerfile")?)],
+            ..Default::default()
+        },
+        "express" => StackConfig {
+            id: "express".to_string(),
+            name: "Express".to_string(),
+            description: "Express API written in TypeScript".to_string(),
+            extends: vec!["node-api-base".to_string()],
+            makefile: Some(MakeFragment::parse(&embedded::load("express/express.mk")?)),
+            next_steps: vec![
+                NextStep::new("GET /health", "Expose it for the Docker healthcheck"),
+                NextStep::new("make dev-express", "Start the server with tsx watch"),
+                NextStep::new("make build-express", "Compile to dist/ with tsc"),
+                NextStep::new("docker build .", "Build the non-root production image"),
+            ],
+            variables: vec![TemplateVariable::override_default(
+                "build_command",
+                "pnpm exec tsc -p tsconfig.json",
+            )],
+            ..Default::default()
+        },
+        "nestjs" => StackConfig {
+            id: "nestjs".to_string(),
+            name: "NestJS".to_string(),
+            description: "NestJS API".to_string(),
+            extends: vec!["node-api-base".to_string()],
+            makefile: Some(MakeFragment::parse(&embedded::load("nestjs/nestjs.mk")?)),
+            next_steps: vec![
+                NextStep::new("GET /health", "Expose it for the Docker healthcheck"),
+                NextStep::new("make dev-nestjs", "Start nest in watch mode"),
+                NextStep::new("make build-nestjs", "Compile to dist/ with nest build"),
+                NextStep::new("docker build .", "Build the non-root production image"),
+            ],
+            variables: vec![
+                TemplateVariable::override_default("build_command", "pnpm exec nest build"),
+                TemplateVariable::override_default("entrypoint", "dist/main.js"),
+            ],
+            ..Default::default()
+        },
+        "fastapi" => StackConfig {
+            id: "fastapi".to_string(),
+            name: "FastAPI".to_string(),
+            description: "REST API with FastAPI and Python".to_string(),
+            extends: vec!["python-base".to_string()],
+            next_steps: vec![
+                NextStep::new("make validate-fastapi", "Check the Python toolchain"),
+                NextStep::new("make dev-fastapi", "Start uvicorn with reload"),
+                NextStep::new("make lint-fastapi", "Run ruff check and format"),
+                NextStep::new(
+                    "docker build --target dev .",
+                    "Build the uvicorn dev image (serves /health)",
+                ),
+            ],
+            variables: vec![
+                TemplateVariable::override_default(
+                    "dev_command",
+                    "uv run uvicorn main:app --host 0.0.0.0 --port ${APP_PORT} --reload",
+                ),
+                TemplateVariable::override_default(
+                    "prod_command",
+                    "uvicorn main:app --host 0.0.0.0 --port ${APP_PORT}",
+                ),
+            ],
+            scaffold: Some(Scaffold {
+                files: vec![
+                    FileTemplate {
+                        path: "pyproject.toml".to_string(),
+                        content: embedded::load("fastapi/scaffold/pyproject.toml")?,
+                    },
+                    FileTemplate {
+                        path: "main.py".to_string(),
+                        content: embedded::load("fastapi/scaffold/main.py")?,
+                    },
+                ],
+                next_steps: vec![NextStep::new("uv lock", "Create uv.lock")],
+            }),
+            ..Default::default()
+        },
+        "django" => StackConfig {
+            id: "django".to_string(),
+            name: "Django".to_string(),
+            description: "Django application served by gunicorn".to_string(),
+            extends: vec!["python-base".to_string()],
+            makefile: Some(MakeFragment::parse(&embedded::load("django/django.mk")?)),
+            next_steps: vec![
+                NextStep::new("uv add gunicorn", "Production WSGI server used by the image"),
+                NextStep::new("GET /health", "Expose it for the Docker healthcheck"),
+                NextStep::new("make migrate", "Apply database migrations"),
+                NextStep::new("make dev-django", "Start manage.py runserver"),
+                NextStep::new(
+                    "docker build --target prod .",
+                    "Build the gunicorn production image",
+                ),
+            ],
+            variables: vec![
+                TemplateVariable::new(
+                    "django_project",
+                    "config",
+                    "Django project package containing wsgi.py",
+                ),
+                TemplateVariable::override_default(
+                    "dev_command",
+                    "uv run python manage.py runserver 0.0.0.0:${APP_PORT}",
+                ),
+                TemplateVariable::override_default(
+                    "build_step",
+                    "# Collect static files (requires STATIC_ROOT in settings)\n\
+                     RUN uv run python manage.py collectstatic --noinput",
+                ),
+                TemplateVariable::override_default(
+                    "prod_command",
+                    "gunicorn {{django_project}}.wsgi:application --bind 0.0.0.0:${APP_PORT}",
+                ),
+                TemplateVariable::override_default("clean_dirs", "staticfiles"),
+                TemplateVariable::override_default(
+                    "app_sources",
+                    "manage.py {{django_project}} staticfiles",
+                ),
+            ],
+            ..Default::default()
+        },
+        "flask" => StackConfig {
+            id: "flask".to_string(),
+            name: "Flask".to_string(),
+            description: "Flask application served by gunicorn".to_string(),
+            extends: vec!["python-base".to_string()],
+            makefile: Some(MakeFragment::parse(&embedded::load("flask/flask.mk")?)),
+            next_steps: vec![
+                NextStep::new("uv add gunicorn", "Production WSGI server used by the image"),
+                NextStep::new("GET /health", "Expose it for the Docker healthcheck"),
+                NextStep::new("make migrate", "Apply Flask-Migrate migrations"),
+                NextStep::new("make dev-flask", "Start the Flask debug server"),
+                NextStep::new(
+                    "docker build --target prod .",
+                    "Build the gunicorn production image",
+                ),
+            ],
+            variables: vec![
+                TemplateVariable::new(
+                    "flask_app",
+                    "app:app",
+                    "Flask application as module:variable",
+                ),
+                TemplateVariable::override_default(
+                    "dev_command",
+                    "uv run flask --app {{flask_app}} run --host 0.0.0.0 --port ${APP_PORT} --debug",
+                ),
+                TemplateVariable::override_default(
+                    "prod_command",
+                    "gunicorn '{{flask_app}}' --bind 0.0.0.0:${APP_PORT}",
+                ),
+                TemplateVariable::override_default("app_sources", "app.py"),
+            ],
+            ..Default::default()
+        },
+        "rust" => StackConfig {
+            id: "rust".to_string(),
+            name: "Rust".to_string(),
+            description: "Rust service built with cargo".to_string(),
+            // Both own Cargo.toml and target/
+            conflicts: vec!["wasm".to_string()],
+            files: vec![
+                FileTemplate {
+                    path: "Dockerfile".to_string(),
+                    content: embedded::load("rust/Dockerfile")?,
+                },
+                FileTemplate {
+                    path: ".dockerignore".to_string(),
+                    content: embedded::load("rust/.dockerignore")?,
+                },
+                FileTemplate {
+                    path: ".env.example".to_string(),
+                    content: embedded::load("rust/.env.example")?,
+                },
+                FileTemplate {
+                    path: ".tool-versions".to_string(),
+                    content: embedded::load("rust/.tool-versions")?,
+                },
+                FileTemplate {
+                    path: "rust-toolchain.toml".to_string(),
+                    content: embedded::load("rust/rust-toolchain.toml")?,
+                },
+            ],
+            makefile: Some(MakeFragment::parse(&embedded::load("rust/rust.mk")?)),
+            next_steps: vec![
+                NextStep::new("make validate-rust", "Check cargo, clippy and rustfmt"),
+                NextStep::new("make test-rust", "Run the test suite"),
+                NextStep::new("make lint-rust", "Run rustfmt and clippy"),
+                NextStep::new(
+                    "docker build .",
+                    "Build the distroless image of the Cargo.toml package binary",
+                ),
+            ],
+            variables: vec![
+                TemplateVariable::new("app_port", "8080", "Port the application listens on"),
+                TemplateVariable::new(
+                    "app_name",
+                    "",
+                    "Binary built into the image, empty for the package name in Cargo.toml",
+                ),
+                TemplateVariable::new(
+                    "rust_version",
+                    "1.90.0",
+                    "Full Rust release of the build image, pinned in rust-toolchain.toml",
+                ),
+            ],
+            hardening: vec![pin_runtime_image(
+                "FROM gcr.io/distroless/cc-debian12:nonroot AS prod",
+            )],
+            api: true,
+            editor_extensions: vec![
+                "rust-lang.rust-analyzer".to_string(),
+                "tamasfe.even-better-toml".to_string(),
+            ],
+            dev_image: Some("docker.io/rust:{{rust_version}}".to_string()),
+            ..Default::default()
+        },
+        "go" => StackConfig {
+            id: "go".to_string(),
+            name: "Go".to_string(),
+            description: "Go service compiled to a static binary".to_string(),
+            files: vec![
+                FileTemplate {
+                    path: "Dockerfile".to_string(),
+                    content: embedded::load("go/Dockerfile")?,
+                },
+                FileTemplate {
+                    path: ".dockerignore".to_string(),
+                    content: embedded::load("go/.dockerignore")?,
+                },
+                FileTemplate {
+                    path: ".env.example".to_string(),
+                    content: embedded::load("go/.env.example")?,
+                },
+                FileTemplate {
+                    path: ".tool-versions".to_string(),
+                    content: embedded::load("go/.tool-versions")?,
+                },
+            ],
+            makefile: Some(MakeFragment::parse(&embedded::load("go/go.mk")?)),
+            next_steps: vec![
+                NextStep::new("make validate-go", "Check go and golangci-lint"),
+                NextStep::new("make test-go", "Run go test ./..."),
+                NextStep::new("make lint-go", "Run go vet and golangci-lint"),
+                NextStep::new(
+                    "docker build --build-arg GO_MAIN=./cmd/<name> .",
+                    "Build the distroless image",
+                ),
+            ],
+            variables: vec![
+                TemplateVariable::new("app_port", "8080", "Port the application listens on"),
+                TemplateVariable::new("go_version", "1.23", "Go version of the build image"),
+            ],
+            hardening: vec![pin_runtime_image(
+                "FROM gcr.io/distroless/static-debian12:nonroot AS prod",
+            )],
+            api: true,
+            editor_extensions: vec!["golang.go".to_string()],
+            dev_image: Some("docker.io/golang:{{go_version}}".to_string()),
+            ..Default::default()
+        },
+        "wasm" => StackConfig {
+            id: "wasm".to_string(),
+            name: "WebAssembly".to_string(),
+            description: "Rust compiled to WebAssembly with trunk or wasm-pack".to_string(),
+            files: vec![
+                FileTemplate {
+                    path: "Dockerfile".to_string(),
+                    content: embedded::load("wasm/Dockerfile")?,
+                },
+                FileTemplate {
+                    path: ".dockerignore".to_string(),
+                    content: embedded::load("wasm/.dockerignore")?,
+                },
+                FileTemplate {
+                    path: ".env.example".to_string(),
+                    content: embedded::load("wasm/.env.example")?,
+                },
+                FileTemplate {
+                    path: ".tool-versions".to_string(),
+                    content: embedded::load("wasm/.tool-versions")?,
+                },
+                FileTemplate {
+                    path: "rust-toolchain.toml".to_string(),
+                    content: embedded::load("wasm/rust-toolchain.toml")?,
+                },
+                nginx_conf()?,
+            ],
+            makefile: Some(MakeFragment::parse(&embedded::load("wasm/wasm.mk")?)),
+            next_steps: vec![
+                NextStep::new(
+                    "make validate-wasm",
+                    "Check the wasm32 target and build tool",
+                ),
+                NextStep::new("make dev-wasm", "Serve the app with live rebuilds"),
+                NextStep::new("make build-wasm", "Build the release bundle"),
+                NextStep::new(
+                    "docker build --target prod .",
+                    "Build the nginx production image",
+                ),
+            ],
+            variables: vec![
+                TemplateVariable::new("wasm_tool", "trunk", "Build tool for the WebAssembly bundle")
+                    .with_choices(&["trunk", "wasm-pack"]),
+                TemplateVariable::new("app_port", "80", "Port nginx serves the bundle on"),
+                TemplateVariable::new(
+                    "host_port",
+                    "8080",
+                    "Unprivileged host port compose.yaml publishes app_port on",
+                ),
+                TemplateVariable::new(
+                    "dev_port",
+                    "8080",
+                    "Port the development server listens on",
+                ),
+                TemplateVariable::new(
+                    "rust_version",
+                    "1.90.0",
+                    "Full Rust release of the build image, pinned in rust-toolchain.toml",
+                ),
+                api_upstream(),
+                nginx_resolver(),
+            ],
+            hardening: vec![
+                harden_prod_stage(embedded::load("wasm/hardened.Dockerfile")?),
+                nginx_listen_unprivileged(),
+            ],
+            editor_extensions: vec![
+                "rust-lang.rust-analyzer".to_string(),
+                "tamasfe.even-better-toml".to_string(),
+            ],
+            dev_image: Some("docker.io/rust:{{rust_version}}".to_string()),
+            ..Default::default()
+        },
+        _ => return Ok(None),
+    };
+    Ok(Some(config))
+}
@@ -0,0 +1,279 @@
+use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{sync::read_installed, StackSpec};
+
+/// Toolchain pinned both locally and in the Dockerfile
+struct Toolchain {
+    /// Build argument holding the version
+    arg: &'static str,
+    /// Base images tagged with the version
+    images: &'static [&'static str],
+    /// Single-version pin files
+    files: &'static [&'static str],
+    /// Names in `.tool-versions`
+    tools: &'static [&'static str],
+    /// Pins must repeat the Dockerfile version, as a shorter one resolves to
+    /// another release locally (rustup reads `1.90` as the latest 1.90.x)
+    exact: bool,
+}
+
+const TOOLCHAINS: [Toolchain; 6] = [
+    Toolchain {
+        arg: "NODE_VERSION",
+        images: &["node"],
+        files: &[".nvmrc", ".node-version"],
+        tools: &["nodejs", "node"],
+        exact: false,
+    },
+    Toolchain {
+        arg: "PNPM_VERSION",
+        images: &[],
+        files: &[],
+        tools: &["pnpm"],
+        exact: false,
+    },
+    Toolchain {
+        arg: "PYTHON_VERSION",
+        images: &["python"],
+        files: &[".python-version"],
+        tools: &["python"],
+        exact: false,
+    },
+    Toolchain {
+        arg: "UV_VERSION",
+        images: &["ghcr.io/astral-sh/uv"],
+        files: &[],
+        tools: &["uv"],
+        exact: false,
+    },
+    Toolchain {
+        arg: "GO_VERSION",
+        images: &["golang"],
+        files: &[],
+        tools: &["golang", "go"],
+        exact: false,
+    },
+    Toolchain {
+        arg: "RUST_VERSION",
+        images: &["rust"],
+        files: &["rust-toolchain.toml"],
+        tools: &["rust"],
+        exact: true,
+    },
+];
+
+/// Local pin compared with the version in the Dockerfile
+#[derive(Debug)]
+pub struct PinCheck {
+    pub stack: String,
+    /// Pin file relative to the target
+    pub file: String,
+    pub tool: String,
+    pub pinned: String,
+    /// Versions found in the Dockerfile, `ARG` default first
+    pub dockerfile: Vec<String>,
+    /// Only an identical version matches
+    pub exact: bool,
+}
+
+impl PinCheck {
+    /// Every Dockerfile version is the pin or, unless `exact`, a prefix of it
+    /// (`20` matches `20.11.1`)
+    pub fn matches(&self) -> bool {
+        self.dockerfile.iter().all(|version| {
+            if self.exact {
+                version.trim_start_matches('v') == self.pinned.trim_start_matches('v')
+            } else {
+                versions_match(version, &self.pinned)
+            }
+        })
+    }
+}
+
+/// Compare the toolchain pins of every installed stack with its Dockerfile
+pub fn check_pins(target_path: &str) -> Result<Vec<PinCheck>> {
+    let installed = read_installed(target_path)?;
+    let base_path = PathBuf::from(target_path);
+    let mut checks = Vec::new();
+
+    for stack in &installed.stacks {
+        let spec = StackSpec::parse(stack);
+        let dir = spec.path(&base_path);
+        let Ok(dockerfile) = fs::read_to_string(dir.join("Dockerfile")) else {
+            continue;
+        };
+
+        for toolchain in &TOOLCHAINS {
+            let versions = dockerfile_versions(&dockerfile, toolchain);
+            if versions.is_empty() {
+                continue;
+            }
+            for (file, pinned) in local_pins(&dir, toolchain) {
+                checks.push(PinCheck {
+                    stack: spec.stack.to_string(),
+                    file: match spec.dir() {
+                        "." => file,
+                        dir => format!("{}/{}", dir, file),
+                    },
+                    tool: toolchain.tools[0].to_string(),
+                    pinned,
+                    dockerfile: versions.clone(),
+                    exact: toolchain.exact,
+                });
+            }
+        }
+    }
+    Ok(checks)
+}
+
+/// `ARG <NAME>=` default and literal tags of the toolchain base images
+fn dockerfile_versions(dockerfile: &str, toolchain: &Toolchain) -> Vec<String> {
+    let mut versions = Vec::new();
+    for line in dockerfile.lines().map(str::trim) {
+        if let Some(default) = line
+            .strip_prefix("ARG ")
+            .and_then(|arg| arg.strip_prefix(toolchain.arg))
+            .and_then(|arg| arg.strip_prefix('='))
+        {
+            if !versions.iter().any(|v| v == default.trim()) {
+                versions.push(default.trim().to_string());
+            }
+            continue;
+        }
+
+        // FROM <image>:<tag> and COPY --from=<image>:<tag>
+        for word in line.split_whitespace() {
+            let word = word.trim_start_matches("--from=");
+            let Some((image, tag)) = word.rsplit_once(':') else {
+                continue;
+            };
+            let image = image.trim_start_matches("docker.io/");
+            if toolchain.images.contains(&image) && !tag.contains('$') {
+                let version = tag.split('-').next().unwrap_or(tag);
+                if !versions.iter().any(|v| v == version) {
+                    versions.push(version.to_string());
+                }
+            }
+        }
+    }
+    versions
+}
+
+/// Versions pinned in the stack directory, with the file they come from
+fn local_pins(dir: &Path, toolchain: &Toolchain) -> Vec<(String, String)> {
+    let mut pins = Vec::new();
+    for file in toolchain.files {
+        let Ok(content) = fs::read_to_string(dir.join(file)) else {
+            continue;
+        };
+        let version = if *file == "rust-toolchain.toml" {
+            content.lines().find_map(|line| {
+                let (key, value) = line.split_once('=')?;
+                (key.trim() == "channel").then(|| value.trim().trim_matches('"').to_string())
+            })
+        } else {
+            content.lines().next().map(|line| line.trim().to_string())
+        };
+        if let Some(version) = version.filter(|v| !v.is_empty()) {
+            pins.push((file.to_string(), version));
+        }
+    }
+
+    if let Ok(content) = fs::read_to_string(dir.join(".tool-versions")) {
+        for line in content.lines() {
+            let mut words = line.split_whitespace();
+            if let (Some(tool), Some(version)) = (words.next(), words.next()) {
+                if toolchain.tools.contains(&tool) {
+                    pins.push((".tool-versions".to_string(), version.to_string()));
+                }
+            }
+        }
+    }
+    pins
+}
+
+/// Equal once a leading `v` is dropped, or one is a dotted prefix of the other
+fn versions_match(a: &str, b: &str) -> bool {
+    let a = a.trim_start_matches('v');
+    let b = b.trim_start_matches('v');
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    long == short || long.starts_with(&format!("{}.", short))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_versions_by_dotted_prefix() {
+        assert!(versions_match("20", "20.11.1"));
+        assert!(versions_match("v20.11.1", "20.11"));
+        assert!(versions_match("3.13", "3.13"));
+        assert!(!versions_match("3.1", "3.13"));
+        assert!(!versions_match("20", "22.1.0"));
+    }
+
+    #[test]
+    fn checks_local_pins_against_the_dockerfile() {
+        let target = std::env::temp_dir().join(format!("devkit-status-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&target);
+        fs::create_dir_all(target.join("api")).unwrap();
+        fs::write(
+            target.join("Makefile"),
+            "DEVKIT_VERSION := 0.3.0\nDEVKIT_STACKS := vue fastapi@api\n",
+        )
+        .unwrap();
+        fs::write(
+            target.join("Dockerfile"),
+            "ARG NODE_VERSION=22\nFROM docker.io/node:${NODE_VERSION}-alpine AS dev\n",
+        )
+        .unwrap();
+        fs::write(target.join(".nvmrc"), "20\n").unwrap();
+        fs::write(
+            target.join("api/Dockerfile"),
+            "ARG PYTHON_VERSION=3.13\nFROM python:3.13-slim AS prod\n",
+        )
+        .unwrap();
+        fs::write(
+            target.join("api/.tool-versions"),
+            "python 3.13.1\nnodejs 22\n",
+        )
+        .unwrap();
+
+        let checks = check_pins(target.to_str().unwrap()).unwrap();
+        let summary: Vec<_> = checks
+            .iter()
+            .map(|c| (c.file.as_str(), c.pinned.as_str(), c.matches()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (".nvmrc", "20", false),
+                ("api/.tool-versions", "3.13.1", true)
+            ]
+        );
+        assert_eq!(checks[1].dockerfile, ["3.13"]);
+    }
+
+    #[test]
+    fn requires_the_rust_toolchain_to_repeat_the_image_release() {
+        let toolchain = TOOLCHAINS.iter().find(|t| t.arg == "RUST_VERSION").unwrap();
+        let dockerfile = "ARG RUST_VERSION=1.90.0\n\
+                          FROM docker.io/rust:${RUST_VERSION}-slim-bookworm AS chef\n";
+        let versions = dockerfile_versions(dockerfile, toolchain);
+        assert_eq!(versions, ["1.90.0"]);
+
+        let check = |pinned: &str| PinCheck {
+            stack: "rust".to_string(),
+            file: "rust-toolchain.toml".to_string(),
+            tool: "rust".to_string(),
+            pinned: pinned.to_string(),
+            dockerfile: versions.clone(),
+            exact: toolchain.exact,
+        };
+        assert!(check("1.90.0").matches());
+        assert!(!check("1.90").matches());
+    }
+}
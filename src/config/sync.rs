@@ -0,0 +1,233 @@
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::path::PathBuf;
+
+use super::{
+    compat::installed_stacks,
+    component::{component_variables, get_component},
+    generate_makefile, load_stack, stack_file_path, stack_variables, template, write_file,
+    StackSpec, Variables,
+};
+use crate::report::{ReportEvent, Reporter};
+
+/// Stacks and components recorded in a generated Makefile
+#[derive(Debug, Default)]
+pub struct Installed {
+    /// devkit version that wrote the Makefile, unknown for older Makefiles
+    pub version: Option<String>,
+    pub stacks: Vec<String>,
+    pub components: Vec<String>,
+    /// Dockerfiles rendered with the hardened profile
+    pub hardened: bool,
+    /// `--var` overrides of the last `devkit config` or `sync-mk`
+    pub variables: Variables,
+}
+
+/// Read the selection recorded in `<target>/Makefile`. Makefiles written before
+/// the selection was recorded fall back to `STACK` and each `<STACK>_DIR`.
+pub fn read_installed(target_path: &str) -> Result<Installed> {
+    let base_path = PathBuf::from(target_path);
+    let content = fs::read_to_string(base_path.join("Makefile")).with_context(|| {
+        format!(
+            "No Makefile in {}; configure the project with `devkit config` first",
+            base_path.display()
+        )
+    })?;
+    let value = |name: &str| {
+        content.lines().find_map(|line| {
+            let (key, value) = line.split_once(":=")?;
+            (key.trim() == name).then(|| value.trim().to_string())
+        })
+    };
+    let words = |value: Option<String>| {
+        value
+            .map(|v| v.split_whitespace().map(str::to_string).collect())
+            .unwrap_or_default()
+    };
+
+    let mut installed = Installed {
+        version: value("DEVKIT_VERSION"),
+        stacks: words(value("DEVKIT_STACKS")),
+        components: words(value("DEVKIT_COMPONENTS")),
+        hardened: value("DEVKIT_HARDENED").is_some_and(|v| v == "true"),
+        variables: content
+            .lines()
+            .filter_map(|line| line.strip_prefix("# DEVKIT_VAR: ")?.split_once('='))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect(),
+    };
+    if installed.version.is_none() {
+        let dirs = installed_stacks(&base_path);
+        installed.stacks = words(value("STACK"))
+            .into_iter()
+            .map(|id: String| match dirs.iter().find(|(i, _)| *i == id) {
+                Some((_, dir)) if dir != "." => format!("{}@{}", id, dir),
+                _ => id,
+            })
+            .collect();
+    }
+    if installed.stacks.is_empty() {
+        return Err(anyhow!(
+            "No stacks recorded in {}",
+            base_path.join("Makefile").display()
+        ));
+    }
+    Ok(installed)
+}
+
+/// Rewrite the `.mk` files and Dockerfiles of an installed project from the
+/// templates embedded in this binary; other files are left untouched.
+/// `variables` take precedence over the overrides recorded in the Makefile.
+pub fn sync_makefiles(
+    target_path: &str,
+    variables: &Variables,
+    reporter: &mut dyn Reporter,
+) -> Result<Installed> {
+    let mut installed = read_installed(target_path)?;
+    installed.variables.extend(variables.clone());
+    let variables = &installed.variables;
+    let base_path = PathBuf::from(target_path);
+    let version = env!("CARGO_PKG_VERSION");
+
+    match installed.version.as_deref() {
+        Some(previous) if previous != version => reporter.report(ReportEvent::Warning {
+            message: format!("Updating files from devkit {} to {}", previous, version),
+        }),
+        None => reporter.report(ReportEvent::Warning {
+            message: format!(
+                "Makefile predates devkit sync-mk; selection read from STACK: {}",
+                installed.stacks.join(" ")
+            ),
+        }),
+        _ => {}
+    }
+
+    for stack in &installed.stacks {
+        let mut config = load_stack(stack)?;
+        if installed.hardened {
+            config = config.with_hardening()?;
+        }
+        let spec = StackSpec::parse(stack);
+        let vars = stack_variables(&config, &spec, &base_path, variables)?;
+        let makefile_path = config.makefile_path();
+
+        reporter.report(ReportEvent::StackStarted {
+            stack: config.display_name(),
+            target: spec.path(&base_path).display().to_string(),
+        });
+        for file in config
+            .files
+            .iter()
+            .filter(|f| f.path == makefile_path || f.path.ends_with("Dockerfile"))
+        {
+            write_file(
+                &stack_file_path(&config, &base_path, &spec, &file.path),
+                &template::render(&file.content, &vars),
+                reporter,
+            )
+            .context(format!("Error updating {}", file.path))?;
+        }
+        reporter.report(ReportEvent::StackCompleted {
+            stack: config.display_name(),
+        });
+    }
+
+    for id in &installed.components {
+        let component = get_component(id)?;
+        let Some(makefile_path) = component.makefile() else {
+            continue;
+        };
+        let vars = component_variables(&component, &installed.stacks, &base_path, variables)?;
+        reporter.report(ReportEvent::ComponentStarted {
+            component: component.name.clone(),
+        });
+        for file in component.files.iter().filter(|f| f.path == makefile_path) {
+            write_file(
+                &base_path.join(&file.path),
+                &template::render(&file.content, &vars),
+                reporter,
+            )
+            .context(format!("Error updating {}", file.path))?;
+        }
+    }
+
+    generate_makefile(
+        &installed.stacks,
+        &installed.components,
+        target_path,
+        variables,
+        installed.hardened,
+        reporter,
+    )?;
+    Ok(installed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Empty target directory holding `files`
+    fn target(name: &str, files: &[(&str, &str)]) -> String {
+        let target =
+            std::env::temp_dir().join(format!("devkit-sync-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&target);
+        fs::create_dir_all(&target).unwrap();
+        for (path, content) in files {
+            fs::write(target.join(path), content).unwrap();
+        }
+        target.display().to_string()
+    }
+
+    #[test]
+    fn reads_the_recorded_selection() {
+        let target = target(
+            "recorded",
+            &[(
+                "Makefile",
+                "# Generated by devkit 0.3.0; refresh with `devkit sync-mk`
+DEVKIT_VERSION := 0.3.0
+DEVKIT_STACKS := vue:static@web express@api
+DEVKIT_COMPONENTS := husky gitignore
+DEVKIT_HARDENED := true
+# DEVKIT_VAR: express.app_port=4000
+# DEVKIT_VAR: api_upstream=http://express:4000
+",
+            )],
+        );
+        let installed = read_installed(&target).unwrap();
+        assert_eq!(installed.version.as_deref(), Some("0.3.0"));
+        assert_eq!(installed.stacks, ["vue:static@web", "express@api"]);
+        assert_eq!(installed.components, ["husky", "gitignore"]);
+        assert!(installed.hardened);
+        assert_eq!(installed.variables["express.app_port"], "4000");
+        assert_eq!(installed.variables["api_upstream"], "http://express:4000");
+    }
+
+    #[test]
+    fn falls_back_to_stack_and_dir_variables_of_older_makefiles() {
+        let target = target(
+            "legacy",
+            &[
+                ("Makefile", "PROJECT_NAME := demo\nSTACK := vue express\n"),
+                ("vue.mk", "VUE_DIR ?= .\n"),
+                ("express.mk", "EXPRESS_DIR ?= api\n"),
+            ],
+        );
+        let installed = read_installed(&target).unwrap();
+        assert_eq!(installed.version, None);
+        assert_eq!(installed.stacks, ["vue", "express@api"]);
+        assert!(installed.components.is_empty());
+        assert!(!installed.hardened);
+    }
+
+    #[test]
+    fn rejects_a_makefile_without_stacks() {
+        let missing = target("missing", &[]);
+        let error = read_installed(&missing).unwrap_err().to_string();
+        assert!(error.starts_with("No Makefile in "), "{}", error);
+
+        let empty = target("empty", &[("Makefile", "all:\n\techo hi\n")]);
+        let error = read_installed(&empty).unwrap_err().to_string();
+        assert!(error.starts_with("No stacks recorded in "), "{}", error);
+    }
+}
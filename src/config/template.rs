@@ -0,0 +1,95 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Values substituted into `{{name}}` placeholders
+pub type Variables = BTreeMap<String, String>;
+
+/// Variable a stack exposes to its templates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateVariable {
+    pub name: String,
+    pub default: String,
+    pub description: String,
+    /// Allowed values, empty when free-form
+    pub choices: Vec<String>,
+}
+
+impl TemplateVariable {
+    pub fn new(name: &str, default: &str, description: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            default: default.to_string(),
+            description: description.to_string(),
+            choices: Vec::new(),
+        }
+    }
+
+    /// Change only the default of a variable inherited through `extends`
+    pub fn override_default(name: &str, default: &str) -> Self {
+        Self::new(name, default, "")
+    }
+
+    pub fn with_choices(mut self, choices: &[&str]) -> Self {
+        self.choices = choices.iter().map(|c| c.to_string()).collect();
+        self
+    }
+}
+
+/// Merge stack defaults with user overrides, checking constrained values;
+/// `builtins` (project and stack names) cannot be overridden
+pub fn resolve_variables(
+    declared: &[TemplateVariable],
+    overrides: &Variables,
+    builtins: Variables,
+) -> Result<Variables> {
+    let mut vars = builtins;
+
+    for var in declared {
+        let value = overrides.get(&var.name).unwrap_or(&var.default);
+        if !var.choices.is_empty() && !var.choices.contains(value) {
+            return Err(anyhow!(
+                "Invalid value '{}' for {} (expected one of: {})",
+                value,
+                var.name,
+                var.choices.join(", ")
+            ));
+        }
+        vars.insert(var.name.clone(), value.clone());
+    }
+
+    // Overrides not declared by the stack are still available to templates
+    for (name, value) in overrides {
+        vars.entry(name.clone()).or_insert_with(|| value.clone());
+    }
+
+    Ok(vars)
+}
+
+/// Maximum nesting of placeholders inside variable values
+const RENDER_PASSES: usize = 4;
+
+/// Replace `{{name}}` placeholders; unknown placeholders are left untouched.
+/// Values may themselves contain placeholders, rendered on the next pass.
+pub fn render(content: &str, vars: &Variables) -> String {
+    let mut out = content.to_string();
+    for _ in 0..RENDER_PASSES {
+        let mut next = out.clone();
+        for (name, value) in vars {
+            next = next.replace(&format!("{{{{{}}}}}", name), value);
+        }
+        if next == out {
+            break;
+        }
+        out = next;
+    }
+    out
+}
+
+/// Parse a `KEY=VALUE` command-line argument
+pub fn parse_variable(arg: &str) -> Result<(String, String), String> {
+    arg.split_once('=')
+        .filter(|(key, _)| !key.is_empty())
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .ok_or_else(|| format!("expected KEY=VALUE, got '{}'", arg))
+}
@@ -0,0 +1,352 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::{FileTemplate, StackConfig, TemplateVariable};
+use crate::report::NextStep;
+
+/// Named flavour of a stack, patching some of its files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StackVariant {
+    pub name: String,
+    pub description: String,
+    pub patches: Vec<FilePatch>,
+    /// Replaces the stack next steps when not empty
+    pub next_steps: Vec<NextStep>,
+    /// Replaces the stack hardened profile when not empty
+    pub hardening: Vec<FilePatch>,
+}
+
+impl StackVariant {
+    pub fn new(name: &str, description: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            patches: Vec::new(),
+            next_steps: Vec::new(),
+            hardening: Vec::new(),
+        }
+    }
+
+    pub fn patch(mut self, patch: FilePatch) -> Self {
+        self.patches.push(patch);
+        self
+    }
+
+    pub fn hardening(mut self, patch: FilePatch) -> Self {
+        self.hardening.push(patch);
+        self
+    }
+
+    pub fn next_steps(mut self, steps: Vec<NextStep>) -> Self {
+        self.next_steps = steps;
+        self
+    }
+}
+
+/// Change applied by a variant or the hardened profile to the stack files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum FilePatch {
+    /// Swap the whole file, or add it when the stack does not have it
+    Replace { path: String, content: String },
+    /// Substitute a snippet inside an existing file
+    Substitute {
+        path: String,
+        from: String,
+        to: String,
+    },
+    /// Drop a file the variant does not use
+    Remove { path: String },
+    /// Swap the trailing `stage` of a Dockerfile, located when the patch is
+    /// applied so a template without the marker only fails when it is used
+    ReplaceStage {
+        path: String,
+        stage: Stage,
+        content: String,
+    },
+}
+
+/// Trailing Dockerfile stage, found by its comment marker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Stage {
+    /// `# Stage: prod` or `# Production stage`
+    Prod,
+    /// The nginx prod stage of a static-site Dockerfile
+    NginxProd,
+}
+
+impl Stage {
+    fn markers(self) -> &'static [&'static str] {
+        match self {
+            Stage::Prod => &["# Stage: prod\n", "# Production stage\n"],
+            Stage::NginxProd => &["# Stage: prod\n# Production image with Nginx"],
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Stage::Prod => "prod stage",
+            Stage::NginxProd => "nginx prod stage",
+        }
+    }
+
+    /// Offset of the stage marker in `dockerfile`
+    fn find(self, dockerfile: &str) -> Option<usize> {
+        self.markers()
+            .iter()
+            .find_map(|marker| dockerfile.find(marker))
+    }
+}
+
+impl StackConfig {
+    /// Apply the named variant's patches to this stack
+    pub fn with_variant(mut self, variant_name: &str) -> Result<Self> {
+        let Some(variant) = self
+            .variants
+            .iter()
+            .find(|v| v.name == variant_name)
+            .cloned()
+        else {
+            if self.variants.is_empty() {
+                return Err(anyhow!(
+                    "Variant '{}' not available: {} has no variants",
+                    variant_name,
+                    self.name
+                ));
+            }
+            return Err(anyhow!(
+                "Variant '{}' not available for {} (available: {})",
+                variant_name,
+                self.name,
+                self.variant_names().join(", ")
+            ));
+        };
+
+        self.apply_patches(variant.patches, &format!("Variant '{}'", variant.name))?;
+
+        if !variant.next_steps.is_empty() {
+            self.next_steps = variant.next_steps;
+        }
+        if !variant.hardening.is_empty() {
+            self.hardening = variant.hardening;
+        }
+        self.variant = Some(variant.name);
+        Ok(self)
+    }
+
+    /// Apply the hardened profile: unprivileged user, `--chown` copies and an
+    /// optional `runtime_digest` pinning the runtime image
+    pub fn with_hardening(mut self) -> Result<Self> {
+        if self.hardening.is_empty() {
+            return Err(anyhow!("{} has no hardened profile", self.name));
+        }
+        let label = match &self.variant {
+            Some(variant) => format!("Hardened profile of the {} variant", variant),
+            None => "Hardened profile".to_string(),
+        };
+        let patches = std::mem::take(&mut self.hardening);
+        self.apply_patches(patches, &label)?;
+
+        self.variables.push(TemplateVariable::new(
+            "runtime_digest",
+            "",
+            "Digest pinning the runtime base image (e.g. @sha256:...)",
+        ));
+        self.hardened = true;
+        Ok(self)
+    }
+
+    fn apply_patches(&mut self, patches: Vec<FilePatch>, label: &str) -> Result<()> {
+        for patch in patches {
+            match patch {
+                FilePatch::Replace { path, content } => {
+                    match self.files.iter_mut().find(|f| f.path == path) {
+                        Some(file) => file.content = content,
+                        None => self.files.push(FileTemplate { path, content }),
+                    }
+                }
+                FilePatch::Substitute { path, from, to } => {
+                    let file = self
+                        .files
+                        .iter_mut()
+                        .find(|f| f.path == path)
+                        .ok_or_else(|| anyhow!("{} patches missing {}", label, path))?;
+                    // An empty `from` would match between every character
+                    if from.is_empty() {
+                        return Err(anyhow!("{} has an empty patch for {}", label, path));
+                    }
+                    if !file.content.contains(&from) {
+                        return Err(anyhow!("{} patch does not match {}", label, path));
+                    }
+                    file.content = file.content.replace(&from, &to);
+                }
+                FilePatch::Remove { path } => self.files.retain(|f| f.path != path),
+                FilePatch::ReplaceStage {
+                    path,
+                    stage,
+                    content,
+                } => {
+                    let file = self
+                        .files
+                        .iter_mut()
+                        .find(|f| f.path == path)
+                        .ok_or_else(|| anyhow!("{} patches missing {}", label, path))?;
+                    let start = stage.find(&file.content).ok_or_else(|| {
+                        anyhow!("{} marker not found in {} {}", stage.label(), self.id, path)
+                    })?;
+                    file.content.truncate(start);
+                    file.content.push_str(&content);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn variant_names(&self) -> Vec<String> {
+        self.variants.iter().map(|v| v.name.clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOCKERFILE: &str =
+        "FROM node AS build\n\n# Stage: prod\n# Production image with Nginx\nFROM nginx AS prod\n";
+
+    fn stack(dockerfile: &str) -> StackConfig {
+        StackConfig {
+            id: "site".to_string(),
+            name: "Site".to_string(),
+            files: vec![
+                FileTemplate {
+                    path: "Dockerfile".to_string(),
+                    content: dockerfile.to_string(),
+                },
+                FileTemplate {
+                    path: "nginx.conf".to_string(),
+                    content: "listen 80;".to_string(),
+                },
+            ],
+            variants: vec![StackVariant::new("caddy", "Caddy")
+                .patch(FilePatch::ReplaceStage {
+                    path: "Dockerfile".to_string(),
+                    stage: Stage::NginxProd,
+                    content: "# Stage: prod\nFROM caddy AS prod\n".to_string(),
+                })
+                .patch(FilePatch::Remove {
+                    path: "nginx.conf".to_string(),
+                })
+                .hardening(FilePatch::ReplaceStage {
+                    path: "Dockerfile".to_string(),
+                    stage: Stage::Prod,
+                    content: "# Stage: prod\nFROM caddy AS prod\nUSER caddy\n".to_string(),
+                })],
+            hardening: vec![
+                FilePatch::ReplaceStage {
+                    path: "Dockerfile".to_string(),
+                    stage: Stage::Prod,
+                    content: "# Stage: prod\nFROM nginx-unprivileged AS prod\n".to_string(),
+                },
+                FilePatch::Substitute {
+                    path: "nginx.conf".to_string(),
+                    from: "listen 80;".to_string(),
+                    to: "listen 8080;".to_string(),
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn file<'a>(config: &'a StackConfig, path: &str) -> Option<&'a str> {
+        config
+            .files
+            .iter()
+            .find(|f| f.path == path)
+            .map(|f| f.content.as_str())
+    }
+
+    #[test]
+    fn variant_replaces_the_stage_and_its_hardening() {
+        let config = stack(DOCKERFILE)
+            .with_variant("caddy")
+            .unwrap()
+            .with_hardening()
+            .unwrap();
+        assert_eq!(
+            file(&config, "Dockerfile"),
+            Some("FROM node AS build\n\n# Stage: prod\nFROM caddy AS prod\nUSER caddy\n")
+        );
+        assert_eq!(file(&config, "nginx.conf"), None);
+        assert_eq!(config.variant.as_deref(), Some("caddy"));
+        assert!(config.hardened);
+        assert!(config.variables.iter().any(|v| v.name == "runtime_digest"));
+    }
+
+    #[test]
+    fn hardening_patches_every_file() {
+        let config = stack(DOCKERFILE).with_hardening().unwrap();
+        assert_eq!(
+            file(&config, "Dockerfile"),
+            Some("FROM node AS build\n\n# Stage: prod\nFROM nginx-unprivileged AS prod\n")
+        );
+        assert_eq!(file(&config, "nginx.conf"), Some("listen 8080;"));
+    }
+
+    #[test]
+    fn missing_marker_only_fails_the_profile_using_it() {
+        let config = stack("FROM nginx AS prod\n");
+        assert_eq!(
+            config.with_hardening().unwrap_err().to_string(),
+            "prod stage marker not found in site Dockerfile"
+        );
+        assert_eq!(
+            stack("FROM nginx AS prod\n")
+                .with_variant("caddy")
+                .unwrap_err()
+                .to_string(),
+            "nginx prod stage marker not found in site Dockerfile"
+        );
+    }
+
+    #[test]
+    fn rejects_empty_and_unmatched_substitutions() {
+        let mut config = stack(DOCKERFILE);
+        config.hardening = vec![FilePatch::Substitute {
+            path: "nginx.conf".to_string(),
+            from: String::new(),
+            to: "x".to_string(),
+        }];
+        assert_eq!(
+            config.with_hardening().unwrap_err().to_string(),
+            "Hardened profile has an empty patch for nginx.conf"
+        );
+
+        let mut config = stack(DOCKERFILE);
+        config.hardening = vec![FilePatch::Substitute {
+            path: "nginx.conf".to_string(),
+            from: "listen 443;".to_string(),
+            to: "x".to_string(),
+        }];
+        assert_eq!(
+            config.with_hardening().unwrap_err().to_string(),
+            "Hardened profile patch does not match nginx.conf"
+        );
+    }
+
+    #[test]
+    fn reports_unknown_variants() {
+        assert_eq!(
+            stack(DOCKERFILE)
+                .with_variant("foo")
+                .unwrap_err()
+                .to_string(),
+            "Variant 'foo' not available for Site (available: caddy)"
+        );
+        let mut config = stack(DOCKERFILE);
+        config.variants.clear();
+        assert_eq!(
+            config.with_variant("foo").unwrap_err().to_string(),
+            "Variant 'foo' not available: Site has no variants"
+        );
+    }
+}
@@ -1,46 +1,210 @@
 mod cli;
 mod config;
+mod report;
 mod ui;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use clap::Parser;
-use cli::Cli;
+use cli::{Cli, OutputFormat};
+use report::{JsonReporter, ReportEvent, Reporter, TerminalReporter};
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    if let Some(dir) = cli.templates.or_else(|| {
+        std::env::var_os("DEVKIT_TEMPLATES")
+            .filter(|dir| !dir.is_empty())
+            .map(Into::into)
+    }) {
+        config::set_template_dir(dir)?;
+    }
+
     match cli.command {
         cli::Commands::Init { path } => {
             // Launch the TUI interface
             ui::run_interactive_setup(path)?;
         }
-        cli::Commands::Config { stacks, path } => {
+        cli::Commands::Config {
+            stacks,
+            path,
+            scaffold,
+            hardened,
+            k8s,
+            proxy,
+            components,
+            vars,
+            format,
+        } => {
             let target_path = path.unwrap_or_else(|| ".".to_string());
+            let mut reporter = reporter_for(format);
+
+            // Reject incompatible stacks and components before writing anything
+            for message in config::check_stacks(&stacks, &target_path)? {
+                reporter.report(ReportEvent::Warning { message });
+            }
+            config::check_components(&components, &stacks)?;
+
+            let mut variables = vars.into_iter().collect();
+            // Static stacks proxy /api to a backend selected alongside them
+            config::link_api_upstream(&stacks, &target_path, &mut variables)?;
+            let options = config::ApplyOptions {
+                scaffold,
+                variables,
+                hardened,
+            };
+
+            // Stacks without a port fail before any other file is written
+            if k8s {
+                config::generate_k8s(
+                    &stacks,
+                    &target_path,
+                    &options.variables,
+                    hardened,
+                    reporter.as_mut(),
+                )?;
+            }
 
             // Apply configuration for each stack
             for stack in &stacks {
-                config::apply_stack_config(stack, &target_path)?;
+                config::apply_stack_config(stack, &target_path, &options, reporter.as_mut())?;
             }
 
-            // Generate Makefile with all stacks
-            config::generate_makefile(&stacks, &target_path)?;
+            for component in &components {
+                config::apply_component(
+                    component,
+                    &stacks,
+                    &target_path,
+                    &options.variables,
+                    reporter.as_mut(),
+                )?;
+            }
+
+            // Generate Makefile with all stacks and components
+            config::generate_makefile(
+                &stacks,
+                &components,
+                &target_path,
+                &options.variables,
+                hardened,
+                reporter.as_mut(),
+            )?;
+            let proxy = match proxy {
+                cli::ProxyServer::Caddy => Some(config::Proxy::Caddy),
+                cli::ProxyServer::Traefik => Some(config::Proxy::Traefik),
+                cli::ProxyServer::None => None,
+            };
+            config::generate_compose(
+                &stacks,
+                &target_path,
+                &options.variables,
+                hardened,
+                proxy,
+                reporter.as_mut(),
+            )?;
+            config::generate_devcontainer(
+                &stacks,
+                &target_path,
+                &options.variables,
+                reporter.as_mut(),
+            )?;
+
+            reporter.report(ReportEvent::SetupCompleted {
+                stacks,
+                target: target_path,
+            });
+        }
+        cli::Commands::SyncMk { path, vars, format } => {
+            let target_path = path.unwrap_or_else(|| ".".to_string());
+            let mut reporter = reporter_for(format);
+
+            let installed = config::sync_makefiles(
+                &target_path,
+                &vars.into_iter().collect(),
+                reporter.as_mut(),
+            )?;
 
-            println!(
-                "✓ Configuration for [{}] applied successfully in {}",
-                stacks.join(", "),
-                target_path
-            );
-            println!("✓ Makefile generated with stack configurations");
+            reporter.report(ReportEvent::SetupCompleted {
+                stacks: installed.stacks,
+                target: target_path,
+            });
+        }
+        cli::Commands::Templates {
+            command: cli::TemplatesCommand::Export { dir, format },
+        } => {
+            let mut reporter = reporter_for(format);
+            config::export_templates(&dir, reporter.as_mut())?;
+        }
+        cli::Commands::Status { path } => {
+            let target_path = path.unwrap_or_else(|| ".".to_string());
+            let checks = config::check_pins(&target_path)?;
+
+            println!("Toolchain pins in {}:", target_path);
+            if checks.is_empty() {
+                println!("  (no version pin files found)");
+            }
+            for check in &checks {
+                let (mark, verdict) = if check.matches() {
+                    ("✓", "matches")
+                } else {
+                    ("✗", "disagrees with")
+                };
+                println!(
+                    "  {} {}: {} {} {} the {} Dockerfile ({})",
+                    mark,
+                    check.file,
+                    check.tool,
+                    check.pinned,
+                    verdict,
+                    check.stack,
+                    check.dockerfile.join(", ")
+                );
+            }
+
+            let mismatches = checks.iter().filter(|c| !c.matches()).count();
+            if mismatches > 0 {
+                return Err(anyhow!(
+                    "{} toolchain pin(s) disagree with the Dockerfiles",
+                    mismatches
+                ));
+            }
         }
         cli::Commands::List => {
             // List available stacks
             let stacks = config::get_available_stacks();
             println!("Available stacks:");
             for stack in stacks {
-                println!("  • {}", stack);
+                let variants = config::get_stack_variants(&stack)?;
+                if variants.is_empty() {
+                    println!("  • {}", stack);
+                } else {
+                    println!("  • {} (variants: {})", stack, variants.join(", "));
+                }
+            }
+
+            println!("\nAvailable components (--with):");
+            for id in config::AVAILABLE_COMPONENTS {
+                let component = config::get_component(id)?;
+                if component.compatible.is_empty() {
+                    println!("  • {} - {}", id, component.description);
+                } else {
+                    let stacks = component.supported_stacks()?;
+                    println!(
+                        "  • {} - {} ({})",
+                        id,
+                        component.description,
+                        stacks.join(", ")
+                    );
+                }
             }
         }
     }
 
     Ok(())
 }
+
+fn reporter_for(format: OutputFormat) -> Box<dyn Reporter> {
+    match format {
+        OutputFormat::Text => Box::new(TerminalReporter),
+        OutputFormat::Json => Box::new(JsonReporter),
+    }
+}
@@ -0,0 +1,14 @@
+use super::{ReportEvent, Reporter};
+
+/// One JSON object per line on stdout, for scripting and CI
+#[derive(Default)]
+pub struct JsonReporter;
+
+impl Reporter for JsonReporter {
+    fn report(&mut self, event: ReportEvent) {
+        match serde_json::to_string(&event) {
+            Ok(line) => println!("{}", line),
+            Err(err) => eprintln!("Unable to serialize event: {}", err),
+        }
+    }
+}
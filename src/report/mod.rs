@@ -0,0 +1,85 @@
+mod json;
+mod terminal;
+
+use serde::{Deserialize, Serialize};
+
+pub use json::JsonReporter;
+pub use terminal::TerminalReporter;
+
+/// Progress events emitted while a stack configuration is applied
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ReportEvent {
+    StackStarted { stack: String, target: String },
+    FileCreated { path: String },
+    FileOverwritten { path: String },
+    FileSkipped { path: String, reason: String },
+    TreeSnapshot { title: String, lines: Vec<String> },
+    StackCompleted { stack: String },
+    ComponentStarted { component: String },
+    Warning { message: String },
+    NextSteps { steps: Vec<NextStep> },
+    SetupCompleted { stacks: Vec<String>, target: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NextStep {
+    pub command: String,
+    pub description: String,
+}
+
+impl NextStep {
+    pub fn new(command: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            command: command.into(),
+            description: description.into(),
+        }
+    }
+}
+
+/// Sink receiving progress events instead of printing them directly
+pub trait Reporter {
+    fn report(&mut self, event: ReportEvent);
+}
+
+impl ReportEvent {
+    /// Human-readable rendering shared by the terminal and TUI reporters
+    pub fn to_lines(&self) -> Vec<String> {
+        match self {
+            ReportEvent::StackStarted { stack, target } => {
+                vec![format!("🔧 Configuring stack {} in {}...", stack, target)]
+            }
+            ReportEvent::FileCreated { path } => vec![format!("  ✓ {} created", path)],
+            ReportEvent::FileOverwritten { path } => vec![format!("  ✓ {} overwritten", path)],
+            ReportEvent::FileSkipped { path, reason } => {
+                vec![format!("  • {} skipped ({})", path, reason)]
+            }
+            ReportEvent::TreeSnapshot { title, lines } => {
+                let mut out = vec![format!("📂 {}", title)];
+                out.extend(lines.iter().cloned());
+                out
+            }
+            ReportEvent::StackCompleted { stack } => {
+                vec![format!("✓ Configuration of {} complete!", stack)]
+            }
+            ReportEvent::ComponentStarted { component } => {
+                vec![format!("🧩 Adding component {}...", component)]
+            }
+            ReportEvent::Warning { message } => vec![format!("⚠ {}", message)],
+            ReportEvent::NextSteps { steps } => {
+                let mut out = vec!["📝 Next steps:".to_string()];
+                out.extend(
+                    steps
+                        .iter()
+                        .map(|s| format!("  {:<14} # {}", s.command, s.description)),
+                );
+                out
+            }
+            ReportEvent::SetupCompleted { stacks, target } => vec![format!(
+                "✓ Configuration for [{}] applied successfully in {}",
+                stacks.join(", "),
+                target
+            )],
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use super::{ReportEvent, Reporter};
+
+/// Plain stdout output, used by the non-interactive commands
+#[derive(Default)]
+pub struct TerminalReporter;
+
+impl Reporter for TerminalReporter {
+    fn report(&mut self, event: ReportEvent) {
+        // Keep sections visually separated like the original output
+        if !matches!(
+            event,
+            ReportEvent::FileCreated { .. }
+                | ReportEvent::FileOverwritten { .. }
+                | ReportEvent::FileSkipped { .. }
+        ) {
+            println!();
+        }
+
+        for line in event.to_lines() {
+            println!("{}", line);
+        }
+    }
+}
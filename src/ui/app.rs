@@ -1,17 +1,47 @@
-use super::{state::AppState, tree_builder::build_tree_lines};
+use super::{
+    path_input::{parent_path, PathInput},
+    reporter::TuiReporter,
+    state::{AppState, FailedAction},
+    tree_builder::{build_file_tree_lines, build_tree_entries, build_tree_lines},
+};
 use crate::config;
-use anyhow::Result;
+use anyhow::{Error, Result};
+use std::path::{Path, PathBuf};
 
 pub struct App {
     pub stacks: Vec<String>,
     pub selected: usize,
     pub should_quit: bool,
     pub state: AppState,
-    pub path_input: String,
+    pub path_input: PathInput,
     pub target_path: String,
     pub tree_lines: Vec<String>,
+    /// Directory behind each tree line, `None` for non-browsable lines
+    tree_paths: Vec<Option<PathBuf>>,
+    pub browsing: bool,
+    pub tree_selected: usize,
     pub selected_stack: String,
+    pub variants: Vec<String>,
+    pub variant_selected: usize,
+    /// Components compatible with the selected stack, with their checked state
+    pub components: Vec<(String, bool)>,
+    /// Files the selected stack will write, relative to the target
+    pub produced_files: Vec<String>,
+    /// Non-blocking compatibility notes shown before applying
+    pub warnings: Vec<String>,
+    pub component_selected: usize,
     pub continue_selected: usize,
+    /// Target is empty or missing, so a starter app can be scaffolded
+    pub target_is_empty: bool,
+    pub scaffold: bool,
+    /// Render the Dockerfiles with the hardened profile
+    pub hardened: bool,
+    pub reporter: TuiReporter,
+    pub log_scroll: u16,
+    pub error_chain: Vec<String>,
+    pub error_selected: usize,
+    failed_action: Option<FailedAction>,
+    error_return_state: AppState,
 }
 
 impl App {
@@ -20,14 +50,13 @@ impl App {
     }
 
     pub fn with_path(target_path: String) -> Self {
-        let current_dir = Self::current_directory();
         let tree_lines = if target_path.is_empty() {
-            build_tree_lines(&current_dir, 3)
+            Vec::new()
         } else {
             build_tree_lines(&target_path, 3)
         };
 
-        Self {
+        let mut app = Self {
             stacks: config::get_available_stacks(),
             selected: 0,
             should_quit: false,
@@ -36,12 +65,35 @@ impl App {
             } else {
                 AppState::SelectingStack
             },
-            path_input: String::new(),
+            path_input: PathInput::default(),
             target_path,
             tree_lines,
+            tree_paths: Vec::new(),
+            browsing: false,
+            tree_selected: 0,
             selected_stack: String::new(),
+            variants: Vec::new(),
+            variant_selected: 0,
+            components: Vec::new(),
+            component_selected: 0,
+            produced_files: Vec::new(),
+            warnings: Vec::new(),
             continue_selected: 0,
+            target_is_empty: false,
+            scaffold: false,
+            hardened: false,
+            reporter: TuiReporter::default(),
+            log_scroll: 0,
+            error_chain: Vec::new(),
+            error_selected: 0,
+            failed_action: None,
+            error_return_state: AppState::SelectingPath,
+        };
+
+        if app.target_path.is_empty() {
+            app.update_tree();
         }
+        app
     }
 
     fn current_directory() -> String {
@@ -63,27 +115,190 @@ impl App {
 
     pub fn select(&mut self) -> Result<()> {
         self.selected_stack = self.stacks[self.selected].clone();
-        self.state = AppState::Confirming;
+        // Check against stacks already configured in the target
+        self.warnings = config::check_stacks(&self.project().stacks, &self.target_path)?;
+        self.variants = config::get_stack_variants(&self.selected_stack)?;
+        self.variant_selected = 0;
+        self.target_is_empty = config::is_empty_target(Path::new(&self.target_path));
+        self.scaffold = self.target_is_empty;
+        self.components =
+            config::get_compatible_components(std::slice::from_ref(&self.selected_stack))?
+                .into_iter()
+                .map(|id| (id, false))
+                .collect();
+        self.component_selected = 0;
+
+        if self.variants.is_empty() {
+            self.show_components();
+        } else {
+            self.state = AppState::SelectingVariant;
+        }
         Ok(())
     }
 
+    pub fn next_variant(&mut self) {
+        if self.variant_selected < self.variants.len().saturating_sub(1) {
+            self.variant_selected += 1;
+        }
+    }
+
+    pub fn previous_variant(&mut self) {
+        self.variant_selected = self.variant_selected.saturating_sub(1);
+    }
+
+    /// Turn the selected stack into a `stack:variant` spec
+    pub fn select_variant(&mut self) {
+        let (stack, _) = config::split_stack_spec(&self.selected_stack);
+        self.selected_stack = format!("{}:{}", stack, self.variants[self.variant_selected]);
+        self.show_components();
+    }
+
+    pub fn cancel_variant_selection(&mut self) {
+        self.state = AppState::SelectingStack;
+    }
+
+    /// Offer the component checklist, skipped when nothing fits the stack
+    fn show_components(&mut self) {
+        self.produced_files = config::load_stack(&self.selected_stack)
+            .map(|stack| stack.produced_files("."))
+            .unwrap_or_default();
+        self.state = if self.components.is_empty() {
+            AppState::Confirming
+        } else {
+            AppState::SelectingComponents
+        };
+    }
+
+    pub fn next_component(&mut self) {
+        if self.component_selected < self.components.len().saturating_sub(1) {
+            self.component_selected += 1;
+        }
+    }
+
+    pub fn previous_component(&mut self) {
+        self.component_selected = self.component_selected.saturating_sub(1);
+    }
+
+    pub fn toggle_component(&mut self) {
+        if let Some((_, checked)) = self.components.get_mut(self.component_selected) {
+            *checked = !*checked;
+        }
+    }
+
+    pub fn confirm_components(&mut self) {
+        self.state = AppState::Confirming;
+    }
+
+    pub fn cancel_component_selection(&mut self) {
+        self.state = if self.variants.is_empty() {
+            AppState::SelectingStack
+        } else {
+            AppState::SelectingVariant
+        };
+    }
+
+    pub fn checked_components(&self) -> Vec<String> {
+        self.components
+            .iter()
+            .filter(|(_, checked)| *checked)
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    /// Selection recorded in the target's Makefile with the chosen stack and
+    /// components added; an earlier entry for the same stack is replaced
+    fn project(&self) -> config::Installed {
+        let mut project = config::read_installed(&self.target_path).unwrap_or_default();
+        let selected = config::StackSpec::parse(&self.selected_stack).stack;
+        project
+            .stacks
+            .retain(|spec| config::StackSpec::parse(spec).stack != selected);
+        project.stacks.push(self.selected_stack.clone());
+        for component in self.checked_components() {
+            if !project.components.contains(&component) {
+                project.components.push(component);
+            }
+        }
+        project
+    }
+
     pub fn confirm_and_apply(&mut self) -> Result<()> {
-        config::apply_stack_config(&self.selected_stack, &self.target_path)?;
+        self.reporter.clear();
+        self.log_scroll = 0;
+        // Shared files are regenerated for every stack already in the target
+        let project = self.project();
+        let options = config::ApplyOptions {
+            scaffold: self.scaffold,
+            hardened: self.hardened,
+            variables: project.variables.clone(),
+        };
+        config::apply_stack_config(
+            &self.selected_stack,
+            &self.target_path,
+            &options,
+            &mut self.reporter,
+        )?;
+        for component in self.checked_components() {
+            config::apply_component(
+                &component,
+                &project.stacks,
+                &self.target_path,
+                &options.variables,
+                &mut self.reporter,
+            )?;
+        }
+        config::generate_makefile(
+            &project.stacks,
+            &project.components,
+            &self.target_path,
+            &options.variables,
+            self.hardened,
+            &mut self.reporter,
+        )?;
+        config::generate_compose(
+            &project.stacks,
+            &self.target_path,
+            &options.variables,
+            self.hardened,
+            None,
+            &mut self.reporter,
+        )?;
+        config::generate_devcontainer(
+            &project.stacks,
+            &self.target_path,
+            &options.variables,
+            &mut self.reporter,
+        )?;
+        self.tree_lines = build_file_tree_lines(&self.target_path, 3);
         self.state = AppState::ContinueOrQuit;
         Ok(())
     }
 
+    pub fn toggle_scaffold(&mut self) {
+        self.scaffold = self.target_is_empty && !self.scaffold;
+    }
+
+    pub fn toggle_hardened(&mut self) {
+        self.hardened = !self.hardened;
+    }
+
     pub fn cancel_confirmation(&mut self) {
-        self.state = AppState::SelectingStack;
+        self.state = if !self.components.is_empty() {
+            AppState::SelectingComponents
+        } else if self.variants.is_empty() {
+            AppState::SelectingStack
+        } else {
+            AppState::SelectingVariant
+        };
     }
 
     pub fn reset_for_new_project(&mut self) {
-        let current_dir = Self::current_directory();
         self.path_input.clear();
         self.target_path.clear();
         self.selected = 0;
         self.continue_selected = 0;
-        self.tree_lines = build_tree_lines(&current_dir, 3);
+        self.browsing = false;
+        self.update_tree();
         self.state = AppState::SelectingPath;
     }
 
@@ -97,30 +312,139 @@ impl App {
         self.continue_selected = self.continue_selected.saturating_sub(1);
     }
 
+    pub fn scroll_log_down(&mut self) {
+        let max = self.reporter.log_lines().len().saturating_sub(1) as u16;
+        self.log_scroll = (self.log_scroll + 5).min(max);
+    }
+
+    pub fn scroll_log_up(&mut self) {
+        self.log_scroll = self.log_scroll.saturating_sub(5);
+    }
+
     pub fn confirm_path(&mut self) {
         self.target_path = if self.path_input.is_empty() {
             ".".to_string()
         } else {
-            self.path_input.clone()
+            self.path_input.expanded()
         };
+        self.browsing = false;
         self.state = AppState::SelectingStack;
     }
 
-    pub fn handle_path_input(&mut self, c: char) {
-        self.path_input.push(c);
+    pub fn update_tree(&mut self) {
+        let path = if self.path_input.is_empty() {
+            Self::current_directory()
+        } else {
+            self.path_input.expanded()
+        };
+
+        let entries = build_tree_entries(&path, 3);
+        self.tree_paths = entries
+            .iter()
+            .map(|entry| entry.is_dir.then(|| entry.path.clone()))
+            .collect();
+        self.tree_lines = entries.into_iter().map(|entry| entry.line).collect();
+        self.tree_selected = self
+            .tree_selected
+            .min(self.tree_lines.len().saturating_sub(1));
     }
 
-    pub fn delete_path_char(&mut self) {
-        self.path_input.pop();
+    pub fn start_browsing(&mut self) {
+        self.browsing = true;
+        self.tree_selected = 0;
     }
 
-    pub fn update_tree(&mut self) {
-        let path = if self.path_input.is_empty() {
-            "."
+    pub fn stop_browsing(&mut self) {
+        self.browsing = false;
+    }
+
+    pub fn browse_next(&mut self) {
+        if self.tree_selected < self.tree_lines.len().saturating_sub(1) {
+            self.tree_selected += 1;
+        }
+    }
+
+    pub fn browse_previous(&mut self) {
+        self.tree_selected = self.tree_selected.saturating_sub(1);
+    }
+
+    /// Descend into the highlighted directory, or select the tree root
+    pub fn browse_enter(&mut self) {
+        if self.tree_selected == 0 {
+            self.confirm_path();
+            return;
+        }
+
+        let Some(Some(path)) = self.tree_paths.get(self.tree_selected) else {
+            return;
+        };
+        self.path_input.set(Self::display_path(path));
+        self.tree_selected = 0;
+        self.update_tree();
+    }
+
+    pub fn browse_parent(&mut self) {
+        let current = if self.path_input.is_empty() {
+            ".".to_string()
         } else {
-            &self.path_input
+            self.path_input.expanded()
         };
-        self.tree_lines = build_tree_lines(path, 3);
+        self.path_input.set(parent_path(&current));
+        self.tree_selected = 0;
+        self.update_tree();
+    }
+
+    /// Show paths under the working directory relative to it
+    fn display_path(path: &Path) -> String {
+        let current_dir = PathBuf::from(Self::current_directory());
+        match path.strip_prefix(&current_dir) {
+            Ok(relative) => format!("./{}", relative.display()),
+            Err(_) => path.display().to_string(),
+        }
+    }
+
+    /// Open the error dialog, keeping the path and selected stack intact
+    pub fn show_error(&mut self, err: Error, action: FailedAction) {
+        self.error_chain = err.chain().map(|cause| cause.to_string()).collect();
+        self.error_selected = 0;
+        self.failed_action = Some(action);
+        self.error_return_state = self.state;
+        self.state = AppState::ShowingError;
+    }
+
+    pub fn next_error_option(&mut self) {
+        if self.error_selected < 2 {
+            self.error_selected += 1;
+        }
+    }
+
+    pub fn previous_error_option(&mut self) {
+        self.error_selected = self.error_selected.saturating_sub(1);
+    }
+
+    pub fn error_return_state(&self) -> AppState {
+        self.error_return_state
+    }
+
+    pub fn retry_failed_action(&mut self) {
+        self.state = self.error_return_state;
+        let Some(action) = self.failed_action.take() else {
+            return;
+        };
+
+        let result = match action {
+            FailedAction::SelectStack => self.select(),
+            FailedAction::ApplyStack => self.confirm_and_apply(),
+        };
+        if let Err(err) = result {
+            self.show_error(err, action);
+        }
+    }
+
+    pub fn dismiss_error(&mut self) {
+        self.failed_action = None;
+        self.error_chain.clear();
+        self.state = self.error_return_state;
     }
 
     pub fn cancel_to_path_selection(&mut self) {
@@ -2,12 +2,19 @@ use anyhow::Result;
 use crossterm::event::{self, Event, KeyCode, KeyEventKind};
 use ratatui::Terminal;
 
-use super::{app::App, state::AppState, views};
+use super::{
+    app::App,
+    state::{AppState, FailedAction},
+    views,
+};
 
 pub fn run_app<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
-) -> Result<()> {
+) -> Result<()>
+where
+    B::Error: Send + Sync + 'static,
+{
     loop {
         terminal.draw(|f| views::render(f, app))?;
 
@@ -31,21 +38,45 @@ fn handle_key_event(app: &mut App, key_code: KeyCode) -> Result<()> {
     match app.state {
         AppState::SelectingPath => handle_path_selection(app, key_code),
         AppState::SelectingStack => handle_stack_selection(app, key_code),
+        AppState::SelectingVariant => handle_variant_selection(app, key_code),
+        AppState::SelectingComponents => handle_component_selection(app, key_code),
         AppState::Confirming => handle_confirmation(app, key_code),
         AppState::ContinueOrQuit => handle_continue_or_quit(app, key_code),
+        AppState::ShowingError => handle_error_dialog(app, key_code),
     }
 }
 
 fn handle_path_selection(app: &mut App, key_code: KeyCode) -> Result<()> {
+    if app.browsing {
+        return handle_path_browsing(app, key_code);
+    }
+
     match key_code {
         KeyCode::Char(c) => {
-            app.handle_path_input(c);
+            app.path_input.insert(c);
             app.update_tree();
         }
         KeyCode::Backspace => {
-            app.delete_path_char();
+            app.path_input.delete_before_cursor();
+            app.update_tree();
+        }
+        KeyCode::Delete => {
+            app.path_input.delete_at_cursor();
+            app.update_tree();
+        }
+        KeyCode::Tab => {
+            app.path_input.complete(true);
             app.update_tree();
         }
+        KeyCode::BackTab => {
+            app.path_input.complete(false);
+            app.update_tree();
+        }
+        KeyCode::Left => app.path_input.move_left(),
+        KeyCode::Right => app.path_input.move_right(),
+        KeyCode::Home => app.path_input.move_home(),
+        KeyCode::End => app.path_input.move_end(),
+        KeyCode::Down => app.start_browsing(),
         KeyCode::Enter => app.confirm_path(),
         KeyCode::Esc => app.should_quit = true,
         _ => {}
@@ -53,13 +84,54 @@ fn handle_path_selection(app: &mut App, key_code: KeyCode) -> Result<()> {
     Ok(())
 }
 
+fn handle_path_browsing(app: &mut App, key_code: KeyCode) -> Result<()> {
+    match key_code {
+        KeyCode::Down | KeyCode::Char('j') => app.browse_next(),
+        KeyCode::Up | KeyCode::Char('k') => app.browse_previous(),
+        KeyCode::Enter | KeyCode::Right | KeyCode::Char('l') => app.browse_enter(),
+        KeyCode::Left | KeyCode::Backspace | KeyCode::Char('h') => app.browse_parent(),
+        KeyCode::Esc | KeyCode::Tab => app.stop_browsing(),
+        _ => {}
+    }
+    Ok(())
+}
+
 fn handle_stack_selection(app: &mut App, key_code: KeyCode) -> Result<()> {
     match key_code {
         KeyCode::Char('q') => app.should_quit = true,
         KeyCode::Esc => app.cancel_to_path_selection(),
         KeyCode::Down | KeyCode::Char('j') => app.next(),
         KeyCode::Up | KeyCode::Char('k') => app.previous(),
-        KeyCode::Enter => app.select()?,
+        KeyCode::Enter => {
+            if let Err(err) = app.select() {
+                app.show_error(err, FailedAction::SelectStack);
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_variant_selection(app: &mut App, key_code: KeyCode) -> Result<()> {
+    match key_code {
+        KeyCode::Char('q') => app.should_quit = true,
+        KeyCode::Esc => app.cancel_variant_selection(),
+        KeyCode::Down | KeyCode::Char('j') => app.next_variant(),
+        KeyCode::Up | KeyCode::Char('k') => app.previous_variant(),
+        KeyCode::Enter => app.select_variant(),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_component_selection(app: &mut App, key_code: KeyCode) -> Result<()> {
+    match key_code {
+        KeyCode::Char('q') => app.should_quit = true,
+        KeyCode::Esc => app.cancel_component_selection(),
+        KeyCode::Down | KeyCode::Char('j') => app.next_component(),
+        KeyCode::Up | KeyCode::Char('k') => app.previous_component(),
+        KeyCode::Char(' ') => app.toggle_component(),
+        KeyCode::Enter => app.confirm_components(),
         _ => {}
     }
     Ok(())
@@ -67,8 +139,14 @@ fn handle_stack_selection(app: &mut App, key_code: KeyCode) -> Result<()> {
 
 fn handle_confirmation(app: &mut App, key_code: KeyCode) -> Result<()> {
     match key_code {
-        KeyCode::Enter => app.confirm_and_apply()?,
+        KeyCode::Enter => {
+            if let Err(err) = app.confirm_and_apply() {
+                app.show_error(err, FailedAction::ApplyStack);
+            }
+        }
         KeyCode::Esc => app.cancel_confirmation(),
+        KeyCode::Char('s') => app.toggle_scaffold(),
+        KeyCode::Char('h') => app.toggle_hardened(),
         KeyCode::Char('q') => app.should_quit = true,
         _ => {}
     }
@@ -77,6 +155,8 @@ fn handle_confirmation(app: &mut App, key_code: KeyCode) -> Result<()> {
 
 fn handle_continue_or_quit(app: &mut App, key_code: KeyCode) -> Result<()> {
     match key_code {
+        KeyCode::PageDown => app.scroll_log_down(),
+        KeyCode::PageUp => app.scroll_log_up(),
         KeyCode::Down | KeyCode::Char('j') => app.next_continue(),
         KeyCode::Up | KeyCode::Char('k') => app.previous_continue(),
         KeyCode::Enter => {
@@ -91,3 +171,20 @@ fn handle_continue_or_quit(app: &mut App, key_code: KeyCode) -> Result<()> {
     }
     Ok(())
 }
+
+fn handle_error_dialog(app: &mut App, key_code: KeyCode) -> Result<()> {
+    match key_code {
+        KeyCode::Left | KeyCode::Char('h') => app.previous_error_option(),
+        KeyCode::Right | KeyCode::Char('l') | KeyCode::Tab => app.next_error_option(),
+        KeyCode::Char('r') => app.retry_failed_action(),
+        KeyCode::Char('b') | KeyCode::Esc => app.dismiss_error(),
+        KeyCode::Char('q') => app.should_quit = true,
+        KeyCode::Enter => match app.error_selected {
+            0 => app.retry_failed_action(),
+            1 => app.dismiss_error(),
+            _ => app.should_quit = true,
+        },
+        _ => {}
+    }
+    Ok(())
+}
@@ -1,5 +1,7 @@
 mod app;
 mod event_handler;
+mod path_input;
+mod reporter;
 mod state;
 mod tree_builder;
 mod views;
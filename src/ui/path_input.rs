@@ -0,0 +1,299 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Editable path field with cursor movement and Tab-completion of directories
+#[derive(Default)]
+pub struct PathInput {
+    value: String,
+    /// Cursor position, in chars
+    cursor: usize,
+    completion: Option<Completion>,
+}
+
+/// Completion cycle started by the first Tab press
+struct Completion {
+    base: String,
+    /// Text after the cursor, kept as typed
+    rest: String,
+    matches: Vec<String>,
+    index: usize,
+}
+
+impl PathInput {
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn set(&mut self, value: String) {
+        self.cursor = value.chars().count();
+        self.value = value;
+        self.completion = None;
+    }
+
+    pub fn clear(&mut self) {
+        self.set(String::new());
+    }
+
+    /// Value with a leading `~` replaced by the home directory
+    pub fn expanded(&self) -> String {
+        expand_tilde(&self.value)
+    }
+
+    pub fn insert(&mut self, c: char) {
+        let at = self.byte_index();
+        self.value.insert(at, c);
+        self.cursor += 1;
+        self.completion = None;
+    }
+
+    pub fn delete_before_cursor(&mut self) {
+        if self.cursor == 0 {
+            return;
+        }
+        self.cursor -= 1;
+        let at = self.byte_index();
+        self.value.remove(at);
+        self.completion = None;
+    }
+
+    pub fn delete_at_cursor(&mut self) {
+        if self.cursor < self.value.chars().count() {
+            let at = self.byte_index();
+            self.value.remove(at);
+            self.completion = None;
+        }
+    }
+
+    pub fn move_left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+        self.completion = None;
+    }
+
+    pub fn move_right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.value.chars().count());
+        self.completion = None;
+    }
+
+    pub fn move_home(&mut self) {
+        self.cursor = 0;
+        self.completion = None;
+    }
+
+    pub fn move_end(&mut self) {
+        self.cursor = self.value.chars().count();
+        self.completion = None;
+    }
+
+    /// Complete the path segment before the cursor, cycling through matches
+    /// on repeated calls; text after the cursor is kept
+    pub fn complete(&mut self, forward: bool) {
+        if let Some(completion) = &mut self.completion {
+            let len = completion.matches.len();
+            completion.index = if forward {
+                (completion.index + 1) % len
+            } else {
+                (completion.index + len - 1) % len
+            };
+            let (base, name, rest) = (
+                completion.base.clone(),
+                completion.matches[completion.index].clone(),
+                completion.rest.clone(),
+            );
+            self.replace_segment(&base, &name, &rest);
+            return;
+        }
+
+        let (head, rest) = self.value.split_at(self.byte_index());
+        let (base, prefix) = match head.rfind('/') {
+            Some(i) => head.split_at(i + 1),
+            None => ("", head),
+        };
+        let matches = matching_directories(base, prefix);
+        if matches.is_empty() {
+            return;
+        }
+
+        let (base, rest) = (base.to_string(), rest.to_string());
+        let index = if forward { 0 } else { matches.len() - 1 };
+        self.replace_segment(&base, &matches[index], &rest);
+
+        // A single match is final, several matches start a cycle
+        if matches.len() > 1 {
+            self.completion = Some(Completion {
+                base,
+                rest,
+                matches,
+                index,
+            });
+        }
+    }
+
+    /// Set the value to `base` + `name/` + `rest`, with the cursor after the slash
+    fn replace_segment(&mut self, base: &str, name: &str, rest: &str) {
+        let head = format!("{}{}/", base, name);
+        self.cursor = head.chars().count();
+        self.value = format!("{}{}", head, rest.strip_prefix('/').unwrap_or(rest));
+    }
+
+    fn byte_index(&self) -> usize {
+        self.value
+            .char_indices()
+            .nth(self.cursor)
+            .map(|(i, _)| i)
+            .unwrap_or(self.value.len())
+    }
+}
+
+pub fn expand_tilde(path: &str) -> String {
+    let Some(rest) = path.strip_prefix('~') else {
+        return path.to_string();
+    };
+    if !rest.is_empty() && !rest.starts_with('/') {
+        return path.to_string();
+    }
+
+    match std::env::var("HOME") {
+        Ok(home) => format!("{}{}", home, rest),
+        Err(_) => path.to_string(),
+    }
+}
+
+/// Parent of a typed path, falling back to `..` segments for relative roots
+pub fn parent_path(path: &str) -> String {
+    let path = expand_tilde(path);
+    let trimmed = path.trim_end_matches('/');
+    if trimmed.is_empty() && path.starts_with('/') {
+        return "/".to_string();
+    }
+    if trimmed.is_empty() || trimmed == "." {
+        return "..".to_string();
+    }
+
+    let p = Path::new(trimmed);
+    if p.ends_with("..") {
+        return format!("{}/..", trimmed);
+    }
+
+    match p.parent() {
+        Some(parent) if parent.as_os_str().is_empty() => ".".to_string(),
+        Some(parent) => parent.display().to_string(),
+        None => trimmed.to_string(),
+    }
+}
+
+fn matching_directories(base: &str, prefix: &str) -> Vec<String> {
+    let dir = if base.is_empty() {
+        PathBuf::from(".")
+    } else {
+        PathBuf::from(expand_tilde(base))
+    };
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut matches: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .map(|e| e.file_name().to_string_lossy().into_owned())
+        .filter(|name| name.starts_with(prefix))
+        // Hidden directories only when explicitly asked for
+        .filter(|name| !name.starts_with('.') || prefix.starts_with('.'))
+        .collect();
+    matches.sort();
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fresh directory under the system temp dir holding `dirs`
+    fn fixture(name: &str, dirs: &[&str]) -> String {
+        let root = std::env::temp_dir().join(format!("devkit-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for dir in dirs {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        fs::write(root.join("apps.txt"), "").unwrap();
+        root.display().to_string()
+    }
+
+    fn input(value: &str) -> PathInput {
+        let mut input = PathInput::default();
+        input.set(value.to_string());
+        input
+    }
+
+    #[test]
+    fn completes_a_single_directory_match() {
+        let root = fixture("single", &["apps/web", ".hidden"]);
+        let mut path = input(&format!("{}/ap", root));
+        path.complete(true);
+        assert_eq!(path.value(), format!("{}/apps/", root));
+        path.complete(true);
+        assert_eq!(path.value(), format!("{}/apps/web/", root));
+    }
+
+    #[test]
+    fn cycles_through_matches_until_the_cursor_moves() {
+        let root = fixture("cycle", &["api", "app", "web"]);
+        let mut path = input(&format!("{}/a", root));
+        path.complete(true);
+        assert_eq!(path.value(), format!("{}/api/", root));
+        path.complete(true);
+        assert_eq!(path.value(), format!("{}/app/", root));
+        path.complete(false);
+        assert_eq!(path.value(), format!("{}/api/", root));
+
+        // Moving the cursor ends the cycle, the next Tab completes from scratch
+        path.move_left();
+        path.move_end();
+        path.complete(true);
+        assert_eq!(path.value(), format!("{}/api/", root));
+    }
+
+    #[test]
+    fn completes_the_segment_before_the_cursor() {
+        let root = fixture("cursor", &["web/src"]);
+        let mut path = input(&format!("{}/w/src", root));
+        for _ in 0.."/src".len() {
+            path.move_left();
+        }
+        path.complete(true);
+        assert_eq!(path.value(), format!("{}/web/src", root));
+        assert_eq!(path.cursor(), format!("{}/web/", root).chars().count());
+    }
+
+    #[test]
+    fn expands_only_a_leading_home_tilde() {
+        let home = std::env::var("HOME").unwrap();
+        assert_eq!(expand_tilde("~"), home);
+        assert_eq!(expand_tilde("~/src"), format!("{}/src", home));
+        assert_eq!(expand_tilde("~user/src"), "~user/src");
+        assert_eq!(expand_tilde("src/~"), "src/~");
+    }
+
+    #[test]
+    fn finds_the_parent_of_typed_paths() {
+        assert_eq!(parent_path("/"), "/");
+        assert_eq!(parent_path("/srv/app/"), "/srv");
+        assert_eq!(parent_path(""), "..");
+        assert_eq!(parent_path("."), "..");
+        assert_eq!(parent_path("app"), ".");
+        assert_eq!(parent_path("../.."), "../../..");
+
+        let home = std::env::var("HOME").unwrap();
+        let parent = Path::new(&home).parent().unwrap().display().to_string();
+        assert_eq!(parent_path("~"), parent);
+        assert_eq!(parent_path("~/"), parent);
+    }
+}
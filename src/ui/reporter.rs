@@ -0,0 +1,68 @@
+use crate::report::{NextStep, ReportEvent, Reporter};
+
+/// Outcome of a single file write, as shown on the result screen
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FileStatus {
+    Created,
+    Overwritten,
+    Skipped,
+}
+
+impl FileStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FileStatus::Created => "created",
+            FileStatus::Overwritten => "overwritten",
+            FileStatus::Skipped => "skipped",
+        }
+    }
+}
+
+/// Collects events so they can be rendered inside the TUI rather than
+/// written to stdout behind the alternate screen
+#[derive(Default)]
+pub struct TuiReporter {
+    events: Vec<ReportEvent>,
+}
+
+impl TuiReporter {
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    pub fn log_lines(&self) -> Vec<String> {
+        self.events.iter().flat_map(ReportEvent::to_lines).collect()
+    }
+
+    pub fn file_results(&self) -> Vec<(String, FileStatus)> {
+        self.events
+            .iter()
+            .filter_map(|event| match event {
+                ReportEvent::FileCreated { path } => Some((path.clone(), FileStatus::Created)),
+                ReportEvent::FileOverwritten { path } => {
+                    Some((path.clone(), FileStatus::Overwritten))
+                }
+                ReportEvent::FileSkipped { path, .. } => Some((path.clone(), FileStatus::Skipped)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Steps of the stack followed by those of its components
+    pub fn next_steps(&self) -> Vec<NextStep> {
+        self.events
+            .iter()
+            .filter_map(|event| match event {
+                ReportEvent::NextSteps { steps } => Some(steps.clone()),
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
+}
+
+impl Reporter for TuiReporter {
+    fn report(&mut self, event: ReportEvent) {
+        self.events.push(event);
+    }
+}
@@ -2,6 +2,16 @@
 pub enum AppState {
     SelectingPath,
     SelectingStack,
+    SelectingVariant,
+    SelectingComponents,
     Confirming,
     ContinueOrQuit,
+    ShowingError,
+}
+
+/// Action that failed and can be retried from the error dialog
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FailedAction {
+    SelectStack,
+    ApplyStack,
 }
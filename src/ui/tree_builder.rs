@@ -1,12 +1,41 @@
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// A rendered tree line with the path it points to
+pub struct TreeEntry {
+    pub line: String,
+    pub path: PathBuf,
+    pub is_dir: bool,
+}
 
 pub fn build_tree_lines(path: &str, max_depth: usize) -> Vec<String> {
+    into_lines(build_tree_entries(path, max_depth))
+}
+
+/// Same as `build_tree_lines`, but also lists files
+pub fn build_file_tree_lines(path: &str, max_depth: usize) -> Vec<String> {
+    into_lines(build_entries(path, max_depth, true))
+}
+
+/// Directory-only tree, keeping the path of each line for browsing
+pub fn build_tree_entries(path: &str, max_depth: usize) -> Vec<TreeEntry> {
+    build_entries(path, max_depth, false)
+}
+
+fn into_lines(entries: Vec<TreeEntry>) -> Vec<String> {
+    entries.into_iter().map(|entry| entry.line).collect()
+}
+
+fn build_entries(path: &str, max_depth: usize, include_files: bool) -> Vec<TreeEntry> {
     let mut lines = Vec::new();
     let path = Path::new(path);
 
     if !path.exists() {
-        lines.push("  (path does not exist)".to_string());
+        lines.push(TreeEntry {
+            line: "  (path does not exist)".to_string(),
+            path: path.to_path_buf(),
+            is_dir: false,
+        });
         return lines;
     }
 
@@ -15,19 +44,32 @@ pub fn build_tree_lines(path: &str, max_depth: usize) -> Vec<String> {
         .and_then(|n| n.to_str())
         .unwrap_or(path.to_str().unwrap_or("."));
 
-    lines.push(format!("📂 {} (./)", display_name));
-    build_tree_recursive(path, &mut lines, "", 0, max_depth, "./".to_string());
+    lines.push(TreeEntry {
+        line: format!("📂 {} (./)", display_name),
+        path: path.to_path_buf(),
+        is_dir: true,
+    });
+    build_tree_recursive(
+        path,
+        &mut lines,
+        "",
+        0,
+        max_depth,
+        "./".to_string(),
+        include_files,
+    );
 
     lines
 }
 
 fn build_tree_recursive(
     path: &Path,
-    lines: &mut Vec<String>,
+    lines: &mut Vec<TreeEntry>,
     prefix: &str,
     depth: usize,
     max_depth: usize,
     current_path: String,
+    include_files: bool,
 ) {
     if depth >= max_depth {
         return;
@@ -42,7 +84,7 @@ fn build_tree_recursive(
 
     let filtered_entries: Vec<_> = entries
         .into_iter()
-        .filter(|entry| should_include_entry(entry))
+        .filter(|entry| should_include_entry(entry, include_files))
         .collect();
 
     for (i, entry) in filtered_entries.iter().enumerate() {
@@ -53,10 +95,23 @@ fn build_tree_recursive(
 
         let relative_path = format!("{}{}", current_path, file_name_str);
 
-        lines.push(format!(
-            "{}{}{} {} ({})",
-            prefix, connector, "📁", file_name_str, relative_path
-        ));
+        if !entry.path().is_dir() {
+            lines.push(TreeEntry {
+                line: format!("{}{}📄 {}", prefix, connector, file_name_str),
+                path: entry.path(),
+                is_dir: false,
+            });
+            continue;
+        }
+
+        lines.push(TreeEntry {
+            line: format!(
+                "{}{}{} {} ({})",
+                prefix, connector, "📁", file_name_str, relative_path
+            ),
+            path: entry.path(),
+            is_dir: true,
+        });
 
         let new_prefix = format!("{}{}", prefix, if is_last { "    " } else { "│   " });
         let new_path = format!("{}/", relative_path);
@@ -67,17 +122,18 @@ fn build_tree_recursive(
             depth + 1,
             max_depth,
             new_path,
+            include_files,
         );
     }
 }
 
-fn should_include_entry(entry: &fs::DirEntry) -> bool {
+fn should_include_entry(entry: &fs::DirEntry, include_files: bool) -> bool {
     const EXCLUDED_DIRS: &[&str] = &["node_modules", "target", "dist", "build"];
 
     let file_name = entry.file_name();
     let file_name_str = file_name.to_string_lossy();
 
-    entry.path().is_dir()
+    (include_files || entry.path().is_dir())
         && !file_name_str.starts_with('.')
         && !EXCLUDED_DIRS.contains(&file_name_str.as_ref())
 }
@@ -1,19 +1,35 @@
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
     Frame,
 };
 
-use super::{app::App, state::AppState};
+use std::path::Path;
+
+use super::{app::App, reporter::FileStatus, state::AppState};
+use crate::config;
 
 pub fn render(f: &mut Frame, app: &App) {
-    match app.state {
+    if app.state == AppState::ShowingError {
+        // Keep the screen that failed visible behind the dialog
+        render_state(f, app, app.error_return_state());
+        render_error_dialog(f, app);
+    } else {
+        render_state(f, app, app.state);
+    }
+}
+
+fn render_state(f: &mut Frame, app: &App, state: AppState) {
+    match state {
         AppState::SelectingPath => render_path_selection(f, app),
         AppState::SelectingStack => render_stack_selection(f, app),
+        AppState::SelectingVariant => render_variant_selection(f, app),
+        AppState::SelectingComponents => render_component_selection(f, app),
         AppState::Confirming => render_confirmation(f, app),
         AppState::ContinueOrQuit => render_continue_or_quit(f, app),
+        AppState::ShowingError => {}
     }
 }
 
@@ -35,11 +51,16 @@ fn render_path_selection(f: &mut Frame, app: &App) {
     let input_text = if app.path_input.is_empty() {
         ". (current directory)"
     } else {
-        &app.path_input
+        app.path_input.value()
+    };
+    let input_color = if app.browsing {
+        Color::DarkGray
+    } else {
+        Color::Yellow
     };
 
     let input = Paragraph::new(input_text)
-        .style(Style::default().fg(Color::Yellow))
+        .style(Style::default().fg(input_color))
         .block(
             Block::default()
                 .borders(Borders::ALL)
@@ -47,6 +68,13 @@ fn render_path_selection(f: &mut Frame, app: &App) {
         );
     f.render_widget(input, chunks[1]);
 
+    if !app.browsing {
+        f.set_cursor_position((
+            chunks[1].x + 1 + app.path_input.cursor() as u16,
+            chunks[1].y + 1,
+        ));
+    }
+
     let tree_items: Vec<ListItem> = app
         .tree_lines
         .iter()
@@ -58,14 +86,31 @@ fn render_path_selection(f: &mut Frame, app: &App) {
         })
         .collect();
 
-    let tree = List::new(tree_items).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title("📁 Project Tree"),
-    );
-    f.render_widget(tree, chunks[2]);
+    let tree_title = if app.browsing {
+        "📁 Project Tree (browsing)"
+    } else {
+        "📁 Project Tree"
+    };
+    let tree = List::new(tree_items)
+        .block(Block::default().borders(Borders::ALL).title(tree_title))
+        .highlight_style(
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let mut tree_state =
+        ListState::default().with_selected(app.browsing.then_some(app.tree_selected));
+    f.render_stateful_widget(tree, chunks[2], &mut tree_state);
 
-    let help = create_help("Type path | Enter: Confirm | Esc: Cancel");
+    let help = if app.browsing {
+        create_help("↑/↓: Navigate | Enter/→: Open (root: select) | ←: Parent | Esc: Edit path")
+    } else {
+        create_help(
+            "Type path | Tab: Complete | ←/→/Home/End: Move | ↓: Browse | Enter: Confirm | Esc: Cancel",
+        )
+    };
     f.render_widget(help, chunks[3]);
 }
 
@@ -104,6 +149,87 @@ fn render_stack_selection(f: &mut Frame, app: &App) {
     f.render_widget(help, chunks[2]);
 }
 
+fn render_variant_selection(f: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(10),
+            Constraint::Length(3),
+        ])
+        .split(f.area());
+
+    let title = create_title(
+        &format!("DevKit - {} variants", app.selected_stack),
+        Color::Cyan,
+    );
+    f.render_widget(title, chunks[0]);
+
+    let items: Vec<ListItem> = app
+        .variants
+        .iter()
+        .enumerate()
+        .map(|(i, variant)| create_list_item(variant, i == app.variant_selected))
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("🧩 Select a variant"),
+    );
+    f.render_widget(list, chunks[1]);
+
+    let help = create_help("↑/↓: Navigate | Enter: Select | Esc: Back");
+    f.render_widget(help, chunks[2]);
+}
+
+fn render_component_selection(f: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(10),
+            Constraint::Length(3),
+        ])
+        .split(f.area());
+
+    let title = create_title(
+        &format!("DevKit - Components for {}", app.selected_stack),
+        Color::Cyan,
+    );
+    f.render_widget(title, chunks[0]);
+
+    let items: Vec<ListItem> = app
+        .components
+        .iter()
+        .enumerate()
+        .map(|(i, (id, checked))| {
+            let description = config::get_component(id)
+                .map(|c| c.description)
+                .unwrap_or_default();
+            let text = format!(
+                "[{}] {:<14} {}",
+                if *checked { "x" } else { " " },
+                id,
+                description
+            );
+            create_list_item(&text, i == app.component_selected)
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("🧰 Optional components"),
+    );
+    f.render_widget(list, chunks[1]);
+
+    let help = create_help("↑/↓: Navigate | Space: Toggle | Enter: Continue | Esc: Back");
+    f.render_widget(help, chunks[2]);
+}
+
 fn render_confirmation(f: &mut Frame, app: &App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -118,15 +244,41 @@ fn render_confirmation(f: &mut Frame, app: &App) {
     let title = create_title("DevKit - Confirmation", Color::Cyan);
     f.render_widget(title, chunks[0]);
 
-    let confirmation_text = format!(
+    let mut confirmation_text = format!(
         "Do you want to apply the {} configuration into {}?\n\n\
         The following files will be created:\n\
         • Makefile\n\
-        • Dockerfile\n\
-        • .dockerignore\n\n\
-        This action will create or overwrite these files.",
+        • common.mk\n\
+        • compose.yaml\n\
+        • .devcontainer/devcontainer.json\n",
         app.selected_stack, app.target_path
     );
+    for file in &app.produced_files {
+        confirmation_text.push_str(&format!("• {}\n", file));
+    }
+    confirmation_text.push_str("\nThis action will create or overwrite these files.");
+
+    for warning in &app.warnings {
+        confirmation_text.push_str(&format!("\n⚠ {}", warning));
+    }
+
+    let components = app.checked_components();
+    if !components.is_empty() {
+        confirmation_text.push_str(&format!("\n\nComponents: {}", components.join(", ")));
+    }
+
+    confirmation_text.push_str(&format!(
+        "\n\n[{}] Hardened Dockerfiles: non-root user and --chown copies (press h to toggle)",
+        if app.hardened { "x" } else { " " }
+    ));
+
+    if app.target_is_empty {
+        confirmation_text.push_str(&format!(
+            "\n\nThe target is empty. [{}] Scaffold a minimal {} app (press s to toggle)",
+            if app.scaffold { "x" } else { " " },
+            app.selected_stack
+        ));
+    }
 
     let text = Paragraph::new(confirmation_text)
         .style(Style::default().fg(Color::White))
@@ -134,7 +286,11 @@ fn render_confirmation(f: &mut Frame, app: &App) {
         .block(Block::default().borders(Borders::ALL));
     f.render_widget(text, chunks[1]);
 
-    let help = create_help("Enter: Confirm | Esc: Cancel");
+    let help = if app.target_is_empty {
+        create_help("Enter: Confirm | s: Toggle scaffold | h: Toggle hardened | Esc: Cancel")
+    } else {
+        create_help("Enter: Confirm | h: Toggle hardened | Esc: Cancel")
+    };
     f.render_widget(help, chunks[2]);
 }
 
@@ -144,15 +300,63 @@ fn render_continue_or_quit(f: &mut Frame, app: &App) {
         .margin(2)
         .constraints([
             Constraint::Length(3),
-            Constraint::Length(7),
+            Constraint::Min(10),
+            Constraint::Length(8),
+            Constraint::Length(4),
             Constraint::Length(3),
         ])
         .split(f.area());
 
-    let title = create_title("✓ Setup complete!", Color::Green);
+    let title = create_title(
+        &format!("✓ {} applied to {}", app.selected_stack, app.target_path),
+        Color::Green,
+    );
     f.render_widget(title, chunks[0]);
 
-    let options = vec![
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[1]);
+
+    let left = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(columns[0]);
+
+    render_file_results(f, app, left[0]);
+    render_next_steps(f, app, left[1]);
+
+    let tree_items: Vec<ListItem> = app
+        .tree_lines
+        .iter()
+        .map(|line| {
+            ListItem::new(Line::from(Span::styled(
+                line.clone(),
+                Style::default().fg(Color::Gray),
+            )))
+        })
+        .collect();
+
+    let tree = List::new(tree_items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("📁 Updated Tree"),
+    );
+    f.render_widget(tree, columns[1]);
+
+    let log_lines: Vec<Line> = app
+        .reporter
+        .log_lines()
+        .into_iter()
+        .map(|line| Line::from(Span::styled(line, Style::default().fg(Color::DarkGray))))
+        .collect();
+
+    let log = Paragraph::new(log_lines)
+        .scroll((app.log_scroll, 0))
+        .block(Block::default().borders(Borders::ALL).title("📜 Log"));
+    f.render_widget(log, chunks[2]);
+
+    let options = [
         "🔄 Configure another project (monorepo)",
         "🚪 Quit assistant",
     ];
@@ -180,10 +384,149 @@ fn render_continue_or_quit(f: &mut Frame, app: &App) {
             .borders(Borders::ALL)
             .title("What would you like to do?"),
     );
-    f.render_widget(list, chunks[1]);
+    f.render_widget(list, chunks[3]);
 
-    let help = create_help("↑/↓: Navigate | Enter: Confirm");
-    f.render_widget(help, chunks[2]);
+    let help = create_help("↑/↓: Navigate | PgUp/PgDn: Scroll log | Enter: Confirm");
+    f.render_widget(help, chunks[4]);
+}
+
+fn render_file_results(f: &mut Frame, app: &App, area: Rect) {
+    let items: Vec<ListItem> = app
+        .reporter
+        .file_results()
+        .into_iter()
+        .map(|(path, status)| {
+            let color = match status {
+                FileStatus::Created => Color::Green,
+                FileStatus::Overwritten => Color::Yellow,
+                FileStatus::Skipped => Color::DarkGray,
+            };
+            let relative = Path::new(&path)
+                .strip_prefix(&app.target_path)
+                .map(|p| p.display().to_string())
+                .unwrap_or(path);
+
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("  {:<12}", status.label()),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(relative, Style::default().fg(Color::White)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("📄 Files written"),
+    );
+    f.render_widget(list, area);
+}
+
+fn render_next_steps(f: &mut Frame, app: &App, area: Rect) {
+    let items: Vec<ListItem> = app
+        .reporter
+        .next_steps()
+        .into_iter()
+        .map(|step| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("  {}", step.command),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(
+                    format!("  # {}", step.description),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("📝 Next steps"),
+    );
+    f.render_widget(list, area);
+}
+
+fn render_error_dialog(f: &mut Frame, app: &App) {
+    let area = centered_rect(70, 60, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red))
+        .title("❌ Error");
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(3), Constraint::Length(1)])
+        .split(inner);
+
+    let mut lines = Vec::new();
+    for (i, cause) in app.error_chain.iter().enumerate() {
+        let (prefix, style) = if i == 0 {
+            (
+                String::new(),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            )
+        } else {
+            (
+                format!("{}└─ caused by: ", "  ".repeat(i - 1)),
+                Style::default().fg(Color::White),
+            )
+        };
+        lines.push(Line::from(Span::styled(
+            format!("{}{}", prefix, cause),
+            style,
+        )));
+    }
+
+    let message = Paragraph::new(lines).wrap(Wrap { trim: false });
+    f.render_widget(message, chunks[0]);
+
+    let options = ["Retry (r)", "Back (b)", "Quit (q)"];
+    let mut spans = Vec::new();
+    for (i, option) in options.iter().enumerate() {
+        let style = if i == app.error_selected {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Red)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        spans.push(Span::styled(format!(" {} ", option), style));
+        spans.push(Span::raw("   "));
+    }
+
+    let buttons = Paragraph::new(Line::from(spans)).alignment(Alignment::Center);
+    f.render_widget(buttons, chunks[1]);
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(vertical[1])[1]
 }
 
 fn create_title(text: &str, color: Color) -> Paragraph<'static> {
@@ -0,0 +1,20 @@
+##@ Husky
+
+# Directory of the package.json holding husky, lint-staged and commitlint
+HUSKY_DIR ?= {{component_dir}}
+
+setup-husky: ## Install husky, commitlint and lint-staged git hooks
+	@echo "Installing husky git hooks in $(HUSKY_DIR) with $(JS_PKG_MANAGER)..."
+	cd $(HUSKY_DIR) && $(JS_PKG_MANAGER) add -D husky lint-staged @commitlint/cli @commitlint/config-conventional
+	$(HUSKY_DIR)/node_modules/.bin/husky
+
+lint-staged-husky: ## Run lint-staged on the staged files (pre-commit hook)
+	cd $(HUSKY_DIR) && $(JS_PKG_MANAGER) exec lint-staged
+
+commitlint-husky: ## Check the last commit message with commitlint
+	cd $(HUSKY_DIR) && $(JS_PKG_MANAGER) exec commitlint --from HEAD~1 --to HEAD --verbose
+
+commit-msg-husky: ## Check the message file in COMMIT_MSG_FILE with commitlint (commit-msg hook)
+	cd $(HUSKY_DIR) && $(JS_PKG_MANAGER) exec commitlint --edit "$(abspath $(COMMIT_MSG_FILE))"
+
+hooks: setup-husky
@@ -0,0 +1,14 @@
+##@ pre-commit
+
+setup-pre-commit: ## Install pre-commit git hooks
+	@echo "Installing pre-commit git hooks..."
+	uv tool install pre-commit
+	pre-commit install
+
+run-pre-commit: ## Run pre-commit hooks on all files
+	pre-commit run --all-files
+
+upgrade-pre-commit: ## Update pre-commit hook revisions
+	pre-commit autoupdate
+
+hooks: setup-pre-commit
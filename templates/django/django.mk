@@ -0,0 +1,26 @@
+validate-{{stack}}: ## Validate {{stack_label}} environment
+	@echo "Validating {{stack_label}} environment..."
+	@echo "PY_PKG_MANAGER: $(PY_PKG_MANAGER)"
+	@which $(PY_PKG_MANAGER) > /dev/null || (echo "Error: $(PY_PKG_MANAGER) is not installed" && exit 1)
+	@test -f $({{stack_dir}})/manage.py || (echo "Error: manage.py not found in $({{stack_dir}})" && exit 1)
+	@echo "✓ {{stack_label}} environment valid"
+
+build-{{stack}}: ## Build {{stack_label}} application (collect static files)
+	@echo "Building {{stack_label}} in $({{stack_dir}})"
+	@if [ "$(DOCKER)" = "true" ]; then \
+		docker compose build; \
+	else \
+		cd $({{stack_dir}}) && uv sync && uv run python manage.py collectstatic --noinput; \
+	fi
+
+test-{{stack}}: ## Run {{stack_label}} tests
+	@echo "Testing {{stack_label}} in $({{stack_dir}})"
+	cd $({{stack_dir}}) && uv run python manage.py test
+
+migrate-{{stack}}: ## Apply {{stack_label}} database migrations
+	@echo "Applying {{stack_label}} migrations in $({{stack_dir}})"
+	cd $({{stack_dir}}) && uv run python manage.py migrate
+
+makemigrations-{{stack}}: ## Create {{stack_label}} migrations from model changes
+	@echo "Creating {{stack_label}} migrations in $({{stack_dir}})"
+	cd $({{stack_dir}}) && uv run python manage.py makemigrations
@@ -0,0 +1,14 @@
+EXPRESS_ENTRY ?= src/index.ts
+
+dev-{{stack}}: ## Start {{stack_label}} server with watch mode
+	@echo "Starting {{stack_label}} server in $({{stack_dir}}) with $(JS_PKG_MANAGER)"
+	@if [ "$(DOCKER)" = "true" ]; then \
+		echo "Note: Make sure to access the server via the correct Docker network settings."; \
+		docker compose up -d; \
+	else \
+		cd $({{stack_dir}}) && $(JS_PKG_MANAGER) install && $(JS_PKG_MANAGER) exec tsx watch $(EXPRESS_ENTRY); \
+	fi
+
+build-{{stack}}: ## Compile {{stack_label}} application with tsc
+	@echo "Building {{stack_label}} in $({{stack_dir}}) with $(JS_PKG_MANAGER)"
+	cd $({{stack_dir}}) && $(JS_PKG_MANAGER) install && $(JS_PKG_MANAGER) exec tsc -p tsconfig.json
@@ -0,0 +1,13 @@
+from fastapi import FastAPI
+
+app = FastAPI(title="{{project_name}}")
+
+
+@app.get("/health")
+def health() -> dict[str, str]:
+    return {"status": "ok"}
+
+
+@app.get("/")
+def root() -> dict[str, str]:
+    return {"message": "Hello from {{project_name}}"}
@@ -0,0 +1,9 @@
+FLASK_APP ?= {{flask_app}}
+
+migrate-{{stack}}: ## Apply Flask-Migrate database migrations
+	@echo "Applying {{stack_label}} migrations in $({{stack_dir}})"
+	cd $({{stack_dir}}) && uv run flask --app $(FLASK_APP) db upgrade
+
+makemigrations-{{stack}}: ## Create Flask-Migrate migrations from model changes
+	@echo "Creating {{stack_label}} migrations in $({{stack_dir}})"
+	cd $({{stack_dir}}) && uv run flask --app $(FLASK_APP) db migrate
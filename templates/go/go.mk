@@ -0,0 +1,56 @@
+# Default variables if not defined
+{{stack_dir}} ?= {{stack_path}}
+GO ?= go
+GO_BIN ?= bin/app
+GO_MAIN ?= .
+DOCKER ?= false
+
+validate-{{stack}}: ## Validate Go environment
+	@echo "Validating Go environment..."
+	@which $(GO) > /dev/null || (echo "Error: $(GO) is not installed" && exit 1)
+	@$(GO) version
+	@which golangci-lint > /dev/null || echo "Warning: golangci-lint is not installed"
+	@echo "✓ Go environment valid"
+
+install-{{stack}}: ## Download Go modules
+	cd $({{stack_dir}}) && $(GO) mod download
+
+dev-{{stack}}: ## Run Go application
+	@echo "Running Go application in $({{stack_dir}})"
+	@if [ "$(DOCKER)" = "true" ]; then \
+		echo "Note: Make sure to access the service via the correct Docker network settings."; \
+		docker compose up -d; \
+	else \
+		cd $({{stack_dir}}) && $(GO) run $(GO_MAIN); \
+	fi
+
+build-{{stack}}: ## Build static Go binary
+	@echo "Building Go in $({{stack_dir}})"
+	@if [ "$(DOCKER)" = "true" ]; then \
+		docker compose build; \
+	else \
+		cd $({{stack_dir}}) && CGO_ENABLED=0 $(GO) build -trimpath -ldflags="-s -w" -o $(GO_BIN) $(GO_MAIN); \
+	fi
+
+test-{{stack}}: ## Run Go tests
+	@echo "Testing Go in $({{stack_dir}})"
+	cd $({{stack_dir}}) && $(GO) test ./...
+
+lint-{{stack}}: ## Run go vet and golangci-lint
+	@echo "Linting Go in $({{stack_dir}})"
+	cd $({{stack_dir}}) && $(GO) vet ./...
+	cd $({{stack_dir}}) && golangci-lint run ./... || echo "golangci-lint not available"
+
+format-{{stack}}: ## Format Go code
+	cd $({{stack_dir}}) && $(GO) fmt ./...
+
+clean-{{stack}}: ## Clean Go artifacts
+	@echo "Cleaning Go artifacts..."
+	cd $({{stack_dir}}) && $(GO) clean && rm -rf bin
+	@if [ "$(DOCKER)" = "true" ]; then \
+		docker compose down; \
+	fi
+
+upgrade-{{stack}}: ## Update Go dependencies
+	@echo "Updating Go dependencies..."
+	cd $({{stack_dir}}) && $(GO) get -u ./... && $(GO) mod tidy
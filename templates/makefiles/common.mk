@@ -1,34 +1,14 @@
-.PHONY: help validate dev build test lint clean
+.PHONY: help
 
-help: ## Show available commands
-	@echo "Available commands:"
-	@grep -E '^[a-zA-Z_-]+:.*?## .*$$' $(MAKEFILE_LIST) | \
-		awk 'BEGIN {FS = ":"; prev=""} \
-		{ \
-			file = $$1; \
-			gsub(/^.*\//, "", file); \
-			if (file != prev && prev != "") print ""; \
-			prev = file; \
-			sub(/^[^:]*:/, ""); \
-			split($$0, arr, ":.*?## "); \
-			printf "  \033[36m%-25s\033[0m %s\n", arr[1], arr[2]; \
-		}'
-
-validate: ## Validate environment
-	@echo "Validating environment..."
-	@echo "STACK: $(STACK)"
-
-dev: validate ## Start development server
-	@echo "Starting development server with stack $(STACK)"
+##@ General
 
-build: validate ## Build the project
-	@echo "Building project with stack $(STACK)"
+# Print the `##@` section headers and `## ` target descriptions of the given files
+define print-help
+	@awk 'BEGIN {FS = ":.*## "} \
+		/^##@/ { printf "\n\033[1m%s\033[0m\n", substr($$0, 5); next } \
+		/^[a-zA-Z0-9_-]+:.*## / { printf "  \033[36m%-25s\033[0m %s\n", $$1, $$2 }' $(1)
+endef
 
-test: validate ## Run tests
-	@echo "Running tests for $(STACK)"
-
-lint: validate ## Run linting
-	@echo "Running linting for $(STACK)"
-
-clean: validate ## Clean artifacts
-	@echo "Cleaning artifacts for $(STACK)"
+help: ## Show available commands
+	@echo "Available commands:"
+	$(call print-help,$(MAKEFILE_LIST))
@@ -0,0 +1,24 @@
+validate-{{stack}}: ## Validate {{stack_label}} environment
+	@echo "Validating {{stack_label}} environment..."
+	@echo "JS_PKG_MANAGER: $(JS_PKG_MANAGER)"
+	@which $(JS_PKG_MANAGER) > /dev/null || (echo "Error: $(JS_PKG_MANAGER) is not installed" && exit 1)
+	@test -f $({{stack_dir}})/nest-cli.json || echo "Warning: nest-cli.json not found in $({{stack_dir}})"
+	@echo "✓ {{stack_label}} environment valid"
+
+dev-{{stack}}: ## Start {{stack_label}} server with watch mode
+	@echo "Starting {{stack_label}} server in $({{stack_dir}}) with $(JS_PKG_MANAGER)"
+	@if [ "$(DOCKER)" = "true" ]; then \
+		echo "Note: Make sure to access the server via the correct Docker network settings."; \
+		docker compose up -d; \
+	else \
+		cd $({{stack_dir}}) && $(JS_PKG_MANAGER) install && $(JS_PKG_MANAGER) exec nest start --watch; \
+	fi
+
+build-{{stack}}: ## Build {{stack_label}} application with nest build
+	@echo "Building {{stack_label}} in $({{stack_dir}}) with $(JS_PKG_MANAGER)"
+	cd $({{stack_dir}}) && $(JS_PKG_MANAGER) install && $(JS_PKG_MANAGER) exec nest build
+
+test-{{stack}}: ## Run {{stack_label}} unit and e2e tests
+	@echo "Testing {{stack_label}} in $({{stack_dir}})"
+	cd $({{stack_dir}}) && $(JS_PKG_MANAGER) run test
+	cd $({{stack_dir}}) && $(JS_PKG_MANAGER) run test:e2e || echo "e2e tests not available"
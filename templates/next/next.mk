@@ -0,0 +1,6 @@
+validate-{{stack}}: ## Validate {{stack_label}} environment
+	@echo "Validating {{stack_label}} environment..."
+	@echo "JS_PKG_MANAGER: $(JS_PKG_MANAGER)"
+	@which $(JS_PKG_MANAGER) > /dev/null || (echo "Error: $(JS_PKG_MANAGER) is not installed" && exit 1)
+	@grep -q "standalone" $({{stack_dir}})/next.config.* 2>/dev/null || echo "Warning: set output: 'standalone' in next.config for the Docker image"
+	@echo "✓ {{stack_label}} environment valid"
@@ -0,0 +1,47 @@
+# Default variables if not defined
+{{stack_dir}} ?= {{stack_path}}
+DOCKER ?= false
+
+validate-{{stack}}: ## Validate {{stack_label}} environment
+	@echo "Validating {{stack_label}} environment..."
+	@echo "JS_PKG_MANAGER: $(JS_PKG_MANAGER)"
+	@which $(JS_PKG_MANAGER) > /dev/null || (echo "Error: $(JS_PKG_MANAGER) is not installed" && exit 1)
+	@echo "✓ {{stack_label}} environment valid"
+
+install-{{stack}}: ## Install {{stack_label}} dependencies
+	cd $({{stack_dir}}) && $(JS_PKG_MANAGER) install
+
+dev-{{stack}}: ## Start {{stack_label}} development server
+	@echo "Starting {{stack_label}} development server in $({{stack_dir}}) with $(JS_PKG_MANAGER)"
+	@if [ "$(DOCKER)" = "true" ]; then \
+		echo "Note: Make sure to access the dev server via the correct Docker network settings."; \
+		docker compose up -d; \
+	else \
+		cd $({{stack_dir}}) && $(JS_PKG_MANAGER) install && $(JS_PKG_MANAGER) run dev; \
+	fi
+
+build-{{stack}}: ## Build {{stack_label}} application
+	@echo "Building {{stack_label}} in $({{stack_dir}}) with $(JS_PKG_MANAGER)"
+	cd $({{stack_dir}}) && $(JS_PKG_MANAGER) install && $(JS_PKG_MANAGER) run build
+
+test-{{stack}}: ## Run {{stack_label}} tests
+	@echo "Testing {{stack_label}} in $({{stack_dir}})"
+	cd $({{stack_dir}}) && $(JS_PKG_MANAGER) run test || echo "Test command not available"
+
+lint-{{stack}}: ## Run {{stack_label}} linting
+	@echo "Linting {{stack_label}} in $({{stack_dir}})"
+	cd $({{stack_dir}}) && $(JS_PKG_MANAGER) run lint || echo "Lint command not available"
+	cd $({{stack_dir}}) && $(JS_PKG_MANAGER) run format || echo "Format command not available"
+
+clean-{{stack}}: ## Clean {{stack_label}} artifacts
+	@echo "Cleaning {{stack_label}} artifacts..."
+	@for dir in node_modules {{clean_dirs}}; do \
+		find $({{stack_dir}}) -type d -name "$$dir" -prune -print -exec rm -rf {} + 2>/dev/null || true; \
+	done
+	@if [ "$(DOCKER)" = "true" ]; then \
+		docker compose down; \
+	fi
+
+upgrade-{{stack}}: ## Update {{stack_label}} dependencies
+	@echo "Updating {{stack_label}} dependencies..."
+	cd $({{stack_dir}}) && $(JS_PKG_MANAGER) up --latest || $(JS_PKG_MANAGER) update
@@ -0,0 +1,53 @@
+# Default variables if not defined
+{{stack_dir}} ?= {{stack_path}}
+{{env_prefix}}APP_PORT ?= {{app_port}}
+DOCKER ?= false
+
+validate-{{stack}}: ## Validate {{stack_label}} environment
+	@echo "Validating {{stack_label}} environment..."
+	@echo "PY_PKG_MANAGER: $(PY_PKG_MANAGER)"
+	@which $(PY_PKG_MANAGER) > /dev/null || (echo "Error: $(PY_PKG_MANAGER) is not installed" && exit 1)
+	@echo "✓ {{stack_label}} environment valid"
+
+install-{{stack}}: ## Install {{stack_label}} dependencies
+	cd $({{stack_dir}}) && uv sync
+
+dev-{{stack}}: APP_PORT := $({{env_prefix}}APP_PORT)
+dev-{{stack}}: ## Start {{stack_label}} development server
+	@echo "Starting {{stack_label}} development server in $({{stack_dir}})"
+	@if [ "$(DOCKER)" = "true" ]; then \
+		echo "Note: Make sure to access the dev server via the correct Docker network settings."; \
+		docker compose up -d; \
+	else \
+		cd $({{stack_dir}}) && uv sync && {{dev_command}}; \
+	fi
+
+build-{{stack}}: ## Build {{stack_label}} application
+	@echo "Building {{stack_label}} in $({{stack_dir}})"
+	@if [ "$(DOCKER)" = "true" ]; then \
+		docker compose build; \
+	else \
+		cd $({{stack_dir}}) && uv sync --frozen; \
+	fi
+
+test-{{stack}}: ## Run {{stack_label}} tests
+	@echo "Testing {{stack_label}} in $({{stack_dir}})"
+	cd $({{stack_dir}}) && uv run pytest
+
+lint-{{stack}}: ## Run {{stack_label}} linting
+	@echo "Linting {{stack_label}} in $({{stack_dir}})"
+	cd $({{stack_dir}}) && uv run ruff check --fix || echo "Lint command not available"
+	cd $({{stack_dir}}) && uv run ruff format || echo "Format command not available"
+
+clean-{{stack}}: ## Clean {{stack_label}} artifacts
+	@echo "Cleaning {{stack_label}} artifacts..."
+	@for dir in .venv __pycache__ .pytest_cache .ruff_cache {{clean_dirs}}; do \
+		find $({{stack_dir}}) -type d -name "$$dir" -prune -print -exec rm -rf {} + 2>/dev/null || true; \
+	done
+	@if [ "$(DOCKER)" = "true" ]; then \
+		docker compose down; \
+	fi
+
+upgrade-{{stack}}: ## Update {{stack_label}} dependencies
+	@echo "Updating {{stack_label}} dependencies..."
+	cd $({{stack_dir}}) && uv lock --upgrade && uv sync
@@ -0,0 +1,57 @@
+# Default variables if not defined
+{{stack_dir}} ?= {{stack_path}}
+CARGO ?= cargo
+DOCKER ?= false
+
+validate-{{stack}}: ## Validate Rust environment
+	@echo "Validating Rust environment..."
+	@which $(CARGO) > /dev/null || (echo "Error: $(CARGO) is not installed" && exit 1)
+	@$(CARGO) --version
+	@$(CARGO) clippy --version > /dev/null 2>&1 || echo "Warning: clippy is not installed (rustup component add clippy)"
+	@$(CARGO) fmt --version > /dev/null 2>&1 || echo "Warning: rustfmt is not installed (rustup component add rustfmt)"
+	@echo "✓ Rust environment valid"
+
+install-{{stack}}: ## Fetch Rust dependencies
+	cd $({{stack_dir}}) && $(CARGO) fetch
+
+dev-{{stack}}: ## Run Rust application (reloads with cargo-watch if installed)
+	@echo "Running Rust application in $({{stack_dir}})"
+	@if [ "$(DOCKER)" = "true" ]; then \
+		echo "Note: Make sure to access the service via the correct Docker network settings."; \
+		docker compose up -d; \
+	elif $(CARGO) watch --version > /dev/null 2>&1; then \
+		cd $({{stack_dir}}) && $(CARGO) watch -x run; \
+	else \
+		cd $({{stack_dir}}) && $(CARGO) run; \
+	fi
+
+build-{{stack}}: ## Build Rust application in release mode
+	@echo "Building Rust in $({{stack_dir}})"
+	@if [ "$(DOCKER)" = "true" ]; then \
+		docker compose build; \
+	else \
+		cd $({{stack_dir}}) && $(CARGO) build --release; \
+	fi
+
+lint-{{stack}}: ## Run clippy and rustfmt
+	@echo "Linting Rust in $({{stack_dir}})"
+	cd $({{stack_dir}}) && $(CARGO) fmt --all -- --check
+	cd $({{stack_dir}}) && $(CARGO) clippy --all-targets --all-features -- -D warnings
+
+format-{{stack}}: ## Format Rust code
+	cd $({{stack_dir}}) && $(CARGO) fmt --all
+
+test-{{stack}}: ## Run Rust tests
+	@echo "Testing Rust in $({{stack_dir}})"
+	cd $({{stack_dir}}) && $(CARGO) test --all-features
+
+clean-{{stack}}: ## Clean Rust artifacts
+	@echo "Cleaning Rust artifacts..."
+	cd $({{stack_dir}}) && $(CARGO) clean
+	@if [ "$(DOCKER)" = "true" ]; then \
+		docker compose down; \
+	fi
+
+upgrade-{{stack}}: ## Update Rust dependencies
+	@echo "Updating Rust dependencies..."
+	cd $({{stack_dir}}) && $(CARGO) update
@@ -0,0 +1,65 @@
+# Default variables if not defined
+{{stack_dir}} ?= {{stack_path}}
+# Build tool: "trunk" (Rust front-end app) or "wasm-pack" (library consumed from JS)
+WASM_TOOL ?= {{wasm_tool}}
+WASM_TARGET ?= web
+WASM_OUT_DIR ?= pkg
+WASM_PORT ?= {{dev_port}}
+DOCKER ?= false
+
+validate-{{stack}}: ## Validate WebAssembly environment
+	@echo "Validating WebAssembly environment..."
+	@echo "WASM_TOOL: $(WASM_TOOL)"
+	@which cargo > /dev/null || (echo "Error: cargo is not installed" && exit 1)
+	@which $(WASM_TOOL) > /dev/null || (echo "Error: $(WASM_TOOL) is not installed (cargo install --locked $(WASM_TOOL))" && exit 1)
+	@rustup target list --installed 2>/dev/null | grep -q wasm32-unknown-unknown || (echo "Error: wasm32-unknown-unknown target missing (rustup target add wasm32-unknown-unknown)" && exit 1)
+	@echo "✓ WebAssembly environment valid"
+
+install-{{stack}}: ## Install the wasm32 target, build tool and crate dependencies
+	rustup target add wasm32-unknown-unknown
+	@which $(WASM_TOOL) > /dev/null || cargo install --locked $(WASM_TOOL)
+	cd $({{stack_dir}}) && cargo fetch
+
+dev-{{stack}}: ## Start WebAssembly development server
+	@echo "Starting WebAssembly development in $({{stack_dir}}) with $(WASM_TOOL)"
+	@if [ "$(DOCKER)" = "true" ]; then \
+		docker compose up -d; \
+	elif [ "$(WASM_TOOL)" = "trunk" ]; then \
+		cd $({{stack_dir}}) && trunk serve --address 0.0.0.0 --port $(WASM_PORT); \
+	else \
+		cd $({{stack_dir}}) && wasm-pack build --dev --target $(WASM_TARGET) --out-dir $(WASM_OUT_DIR) && \
+		python3 -m http.server $(WASM_PORT); \
+	fi
+
+build-{{stack}}: ## Build WebAssembly bundle
+	@echo "Building WebAssembly in $({{stack_dir}}) with $(WASM_TOOL)"
+	@if [ "$(WASM_TOOL)" = "trunk" ]; then \
+		cd $({{stack_dir}}) && trunk build --release; \
+	else \
+		cd $({{stack_dir}}) && wasm-pack build --release --target $(WASM_TARGET) --out-dir $(WASM_OUT_DIR); \
+	fi
+
+lint-{{stack}}: ## Run clippy and rustfmt for the wasm target
+	@echo "Linting WebAssembly crate in $({{stack_dir}})"
+	cd $({{stack_dir}}) && cargo fmt --all -- --check
+	cd $({{stack_dir}}) && cargo clippy --target wasm32-unknown-unknown -- -D warnings
+
+test-{{stack}}: ## Run WebAssembly tests
+	@echo "Testing WebAssembly crate in $({{stack_dir}})"
+	@if [ "$(WASM_TOOL)" = "wasm-pack" ]; then \
+		cd $({{stack_dir}}) && wasm-pack test --headless --firefox; \
+	else \
+		cd $({{stack_dir}}) && cargo test; \
+	fi
+
+clean-{{stack}}: ## Clean WebAssembly artifacts
+	@echo "Cleaning WebAssembly artifacts..."
+	cd $({{stack_dir}}) && cargo clean
+	@rm -rf $({{stack_dir}})/dist $({{stack_dir}})/$(WASM_OUT_DIR)
+	@if [ "$(DOCKER)" = "true" ]; then \
+		docker compose down; \
+	fi
+
+upgrade-{{stack}}: ## Update WebAssembly crate dependencies
+	@echo "Updating WebAssembly dependencies..."
+	cd $({{stack_dir}}) && cargo update